@@ -12,7 +12,13 @@ use tmpdir::TmpDir;
 use uuid::Uuid;
 
 use crate::services::{
-    code_generator::{types::TemplateType, CodeGenerator, CodeGeneratorServiceError},
+    code_generator::{
+        types::{
+            PalletConfig, PalletScaffold, SdkVersion, TemplateType, UnbuildableSelection,
+            UnprocessableProject,
+        },
+        CodeGenerator, CodeGeneratorServiceError, ProjectOptions,
+    },
     traits::{object_store::ObjectStoreService, version_control::VersionControlService},
 };
 
@@ -22,6 +28,8 @@ pub struct ParameterConfiguration {
     pub multiplier: Option<i64>,
     /// The unit of the parameter
     pub unit: Option<String>,
+    /// The value of a typed parameter, used instead of the multiplier and unit
+    pub value: Option<String>,
 }
 
 impl Example for ParameterConfiguration {
@@ -29,10 +37,19 @@ impl Example for ParameterConfiguration {
         Self {
             multiplier: Some(123),
             unit: Some("Some unit from provided list".to_string()),
+            value: None,
         }
     }
 }
 
+#[derive(Object, Clone, Serialize, Deserialize)]
+pub struct PalletInstance {
+    /// The runtime name of the instance, e.g. `TechnicalCouncil`
+    pub name: String,
+    /// Optional configuration parameters of this instance
+    pub parameters: Option<HashMap<String, ParameterConfiguration>>,
+}
+
 #[derive(Object, Deserialize, Clone)]
 pub struct GitHubCredentials {
     pub username: String,
@@ -46,11 +63,41 @@ pub struct NewProject {
     /// The list of pallets to include in the project, where the key is the
     /// pallet name and the value is a optional map of configuration parameters
     pub pallets: HashMap<String, Option<HashMap<String, ParameterConfiguration>>>,
+    /// Optional `construct_runtime` indices keyed by pallet name. Pallets
+    /// without one get the lowest index not taken by the template
+    pub pallet_indices: Option<HashMap<String, u8>>,
+    /// Optional named instances of instantiable pallets, keyed by pallet name
+    pub pallet_instances: Option<HashMap<String, Vec<PalletInstance>>>,
+    /// Optional pallets from outside the catalog, described like catalog pallets
+    /// and pulled from their own git repositories
+    pub custom_pallets: Option<Vec<PalletConfig>>,
+    /// Optional new pallets to generate under `pallets/<name>`. Each one is added
+    /// to the runtime as `Pallet <Name>`, which is also the key for configuring
+    /// its constants in `pallets`
+    pub pallet_scaffolds: Option<Vec<PalletScaffold>>,
+    /// Optional `Config` implementations, keyed by pallet name and then by
+    /// associated type, naming one of the pallet's `pallet_trait_alternatives`
+    pub pallet_traits: Option<HashMap<String, HashMap<String, String>>>,
     /// The template type for the project
     pub template: TemplateType,
+    /// The polkadot-sdk release to generate against, `polkadot-v1.14.0` when not set
+    pub sdk_version: Option<SdkVersion>,
     github: Option<GitHubCredentials>,
 }
 
+impl From<&NewProject> for ProjectOptions {
+    fn from(project: &NewProject) -> Self {
+        Self {
+            pallet_indices: project.pallet_indices.clone().unwrap_or_default(),
+            pallet_instances: project.pallet_instances.clone().unwrap_or_default(),
+            custom_pallets: project.custom_pallets.clone().unwrap_or_default(),
+            pallet_scaffolds: project.pallet_scaffolds.clone().unwrap_or_default(),
+            pallet_traits: project.pallet_traits.clone().unwrap_or_default(),
+            sdk_version: project.sdk_version.unwrap_or_default(),
+        }
+    }
+}
+
 impl Example for NewProject {
     fn example() -> Self {
         let mut pallets = HashMap::new();
@@ -60,7 +107,13 @@ impl Example for NewProject {
         Self {
             name: "project_name".to_string(),
             template: TemplateType::SoloChain,
+            sdk_version: Some(SdkVersion::default()),
             pallets,
+            pallet_indices: None,
+            pallet_instances: None,
+            custom_pallets: None,
+            pallet_scaffolds: None,
+            pallet_traits: None,
             github: None,
         }
     }
@@ -71,12 +124,97 @@ pub enum GenerateProjectResponse {
     /// Returns when the user is successfully updated.
     #[oai(status = 200)]
     Ok(Json<Uuid>),
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
     #[oai(status = 404)]
     PalletNotFound(PlainText<String>),
+    /// Lists every parameter configuration that does not fit its pallet, or every rendered file
+    /// that does not parse with the pallet that broke it.
+    #[oai(status = 422)]
+    Unprocessable(Json<UnprocessableProject>),
+    /// Lists every selected or required pallet that the template does not support, or every
+    /// pair of them that cannot be used together.
+    #[oai(status = 409)]
+    UnbuildableSelection(Json<UnbuildableSelection>),
     #[oai(status = 500)]
     InternalServerError(PlainText<String>),
 }
 
+/// Response to a project that cannot be generated, shared by the endpoints that render one.
+pub enum ProjectError {
+    BadRequest(String),
+    PalletNotFound(String),
+    Unprocessable(UnprocessableProject),
+    UnbuildableSelection(UnbuildableSelection),
+    InternalServerError(String),
+}
+
+impl From<CodeGeneratorServiceError> for ProjectError {
+    fn from(e: CodeGeneratorServiceError) -> Self {
+        match e {
+            CodeGeneratorServiceError::PalletIndexError(_)
+            | CodeGeneratorServiceError::DependencyConflictError(_)
+            | CodeGeneratorServiceError::UnsupportedSdkVersion(_)
+            | CodeGeneratorServiceError::PalletInstanceError(_)
+            | CodeGeneratorServiceError::CustomPalletError(_)
+            | CodeGeneratorServiceError::PalletScaffoldError(_)
+            | CodeGeneratorServiceError::PalletTraitError(_) => {
+                ProjectError::BadRequest(e.to_string())
+            }
+            CodeGeneratorServiceError::InvalidParameterConfiguration(invalid_parameters) => {
+                ProjectError::Unprocessable(UnprocessableProject {
+                    invalid_parameters,
+                    ..Default::default()
+                })
+            }
+            CodeGeneratorServiceError::UnsupportedPalletError(unsupported_pallets) => {
+                ProjectError::UnbuildableSelection(UnbuildableSelection {
+                    unsupported_pallets,
+                    ..Default::default()
+                })
+            }
+            CodeGeneratorServiceError::PalletConflictError(conflicts) => {
+                ProjectError::UnbuildableSelection(UnbuildableSelection {
+                    conflicts,
+                    ..Default::default()
+                })
+            }
+            CodeGeneratorServiceError::InvalidGeneratedFileError(invalid_generated_files) => {
+                ProjectError::Unprocessable(UnprocessableProject {
+                    invalid_generated_files,
+                    ..Default::default()
+                })
+            }
+            CodeGeneratorServiceError::PalletNotFoundError(pallet_name) => {
+                ProjectError::PalletNotFound(format!("Pallet not found: {}", pallet_name))
+            }
+            e => ProjectError::InternalServerError(format!("Internal Server Error: {}", e)),
+        }
+    }
+}
+
+impl From<CodeGeneratorServiceError> for GenerateProjectResponse {
+    fn from(e: CodeGeneratorServiceError) -> Self {
+        match ProjectError::from(e) {
+            ProjectError::BadRequest(message) => {
+                GenerateProjectResponse::BadRequest(PlainText(message))
+            }
+            ProjectError::PalletNotFound(message) => {
+                GenerateProjectResponse::PalletNotFound(PlainText(message))
+            }
+            ProjectError::Unprocessable(unprocessable) => {
+                GenerateProjectResponse::Unprocessable(Json(unprocessable))
+            }
+            ProjectError::UnbuildableSelection(unbuildable) => {
+                GenerateProjectResponse::UnbuildableSelection(Json(unbuildable))
+            }
+            ProjectError::InternalServerError(message) => {
+                GenerateProjectResponse::InternalServerError(PlainText(message))
+            }
+        }
+    }
+}
+
 pub async fn generate_a_project_handler(
     task_status_map: Arc<
         ConcurrentHashMap<Uuid, Option<Result<String, CodeGeneratorServiceError>>>,
@@ -89,22 +227,15 @@ pub async fn generate_a_project_handler(
     let project_name = project.name.clone();
 
     let archive = match code_generator_service
-        .generate_project_archive(&project.pallets, &project.template)
+        .generate_project_archive(
+            &project.pallets,
+            &project.template,
+            &ProjectOptions::from(&project.0),
+        )
         .await
     {
         Ok(archive) => archive,
-        Err(CodeGeneratorServiceError::PalletNotFoundError(pallet_name)) => {
-            return GenerateProjectResponse::PalletNotFound(PlainText(format!(
-                "Pallet not found: {}",
-                pallet_name
-            )))
-        }
-        Err(e) => {
-            return GenerateProjectResponse::InternalServerError(PlainText(format!(
-                "Internal Server Error: {}",
-                e
-            )))
-        }
+        Err(e) => return e.into(),
     };
     let status_id = Uuid::new_v4();
     // TODO: hadnle result
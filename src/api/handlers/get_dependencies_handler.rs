@@ -1,11 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use poem_openapi::{
     payload::{Json, PlainText},
     ApiResponse, Object,
 };
 
-use crate::services::code_generator::types::{PalletConfig, TemplateType};
+use crate::services::code_generator::{
+    dependencies::{resolve_pallets, DependencyResolutionError, PalletInclusion},
+    service::pallet_conflicts,
+    types::{PalletConfig, TemplateType},
+};
 
 #[derive(Object)]
 pub struct GetDependenciesRequest {
@@ -15,21 +19,36 @@ pub struct GetDependenciesRequest {
 
 #[derive(ApiResponse)]
 pub enum GetDependenciesResponse {
-    /// Returns when the user is successfully updated.
+    /// Returns every pallet each requested pallet brings in, with the reason it is included.
+    /// The entries used to be bare pallet names, clients reading those need to read `name`.
     #[oai(status = 200)]
-    Ok(Json<HashMap<String, Vec<String>>>),
+    Ok(Json<HashMap<String, Vec<PalletInclusion>>>),
     #[oai(status = 404)]
     NotFound(PlainText<String>),
+    /// Returns when the requested pallets, or pallets they require, cannot be used together.
+    #[oai(status = 409)]
+    Conflict(PlainText<String>),
 }
 
 pub async fn get_dependencies_handler(
     pallet_configs: &HashMap<String, PalletConfig>,
     request: Json<GetDependenciesRequest>,
 ) -> GetDependenciesResponse {
-    // Check if the pallets are supported
-    if let Some(pallets) = &request.pallets {
-        for pallet_name in pallets.iter() {
-            if !pallet_configs.contains_key(pallet_name) {
+    let names = match &request.pallets {
+        None => pallet_configs.keys().cloned().collect::<Vec<_>>(),
+        Some(pallets) => pallets.clone(),
+    };
+    let mut response = HashMap::new();
+    for name in names {
+        match resolve_pallets(
+            pallet_configs,
+            std::slice::from_ref(&name),
+            &request.template,
+        ) {
+            Ok(included) => {
+                response.insert(name, included);
+            }
+            Err(DependencyResolutionError::PalletNotFound(pallet_name)) => {
                 return GetDependenciesResponse::NotFound(PlainText(format!(
                     "Pallet {} not found",
                     pallet_name
@@ -37,37 +56,20 @@ pub async fn get_dependencies_handler(
             }
         }
     }
-    let essential = pallet_configs
-        .iter()
-        .filter_map(|(name, pallet)| match &pallet.metadata.is_essential {
-            None => None,
-            Some(templates) => {
-                if templates.contains(&request.template) {
-                    Some(name.clone())
-                } else {
-                    None
-                }
-            }
-        })
-        .collect::<Vec<String>>();
-    let filtered = match &request.pallets {
-        None => pallet_configs,
-        Some(pallets) => &pallet_configs
-            .iter()
-            .filter(|(name, _)| pallets.contains(name))
-            .map(|(name, config)| (name.clone(), config.clone()))
-            .collect(),
-    };
-    let response = filtered
-        .iter()
-        .map(|(name, config)| {
-            let mut dependencies = essential.clone();
-            dependencies.push(name.clone());
-            if let Some(req_deps) = &config.dependencies.required_pallets {
-                dependencies.extend(req_deps.iter().cloned());
-            }
-            (name.clone(), dependencies)
-        })
-        .collect::<HashMap<_, _>>();
+    if request.pallets.is_some() {
+        let selected = response
+            .values()
+            .flatten()
+            .map(|inclusion| &inclusion.name)
+            .collect::<HashSet<_>>();
+        let conflicts = pallet_conflicts(
+            pallet_configs
+                .values()
+                .filter(|pallet| selected.contains(&pallet.name)),
+        );
+        if !conflicts.is_empty() {
+            return GetDependenciesResponse::Conflict(PlainText(conflicts.join("; ")));
+        }
+    }
     GetDependenciesResponse::Ok(Json(response))
 }
@@ -6,7 +6,9 @@ use poem_openapi::{
     ApiResponse, Object,
 };
 
-use crate::services::code_generator::types::{PalletConfig, ParameterType, TemplateType};
+use crate::services::code_generator::types::{
+    PalletConfig, ParameterType, ParameterValue, SdkVersion, TemplateType,
+};
 
 #[derive(Object)]
 pub struct Parameter {
@@ -17,27 +19,41 @@ pub struct Parameter {
     pub example: String,
     pub default_unit: String,
     pub default_multiplier: Option<i64>,
+    /// Kind, bounds and default of a typed parameter, configured with `value`
+    pub value_type: Option<ParameterValue>,
 }
 
 impl From<&ParameterType> for Parameter {
     fn from(pt: &ParameterType) -> Self {
-        let unit = if !pt.expression.possible_units.is_empty() {
-            "{{unit}}"
-        } else {
-            ""
+        let example_expression = match (&pt.value, &pt.expression) {
+            (Some(_), _) | (None, None) => "{{value}}".to_string(),
+            (None, Some(expression)) => {
+                let unit = if !expression.possible_units.is_empty() {
+                    "{{unit}}"
+                } else {
+                    ""
+                };
+                expression.format.format(&[unit, "{{multiplier}}"])
+            }
         };
-        let example_expression = pt.expression.format.format(&[unit, "{{multiplier}}"]);
+        let expression = pt.expression.as_ref().filter(|_| pt.value.is_none());
         Self {
             name: pt.name.clone(),
             description: pt.description.clone(),
-            possible_unit_names: pt.expression.possible_units.clone(),
-            multiplier_configurable: pt.expression.multiplier_configurable,
+            possible_unit_names: expression
+                .map(|expression| expression.possible_units.clone())
+                .unwrap_or_default(),
+            multiplier_configurable: expression
+                .is_some_and(|expression| expression.multiplier_configurable),
             example: format!(
                 "pub{}{}: {} = {};",
                 pt.prefix, pt.name, pt.p_type, example_expression
             ),
-            default_multiplier: pt.expression.default_multiplier,
-            default_unit: pt.expression.default_unit.clone(),
+            default_multiplier: expression.and_then(|expression| expression.default_multiplier),
+            default_unit: expression
+                .map(|expression| expression.default_unit.clone())
+                .unwrap_or_default(),
+            value_type: pt.value.clone(),
         }
     }
 }
@@ -45,6 +61,8 @@ impl From<&ParameterType> for Parameter {
 pub struct PalletOptionsRequest {
     pub template: TemplateType,
     pub pallets: Vec<String>,
+    /// The polkadot-sdk release to list options for, `polkadot-v1.14.0` when not set
+    pub sdk_version: Option<SdkVersion>,
 }
 #[derive(ApiResponse)]
 pub enum GetPalletOptionsResponse {
@@ -61,12 +79,19 @@ pub async fn get_pallet_options_handler(
 ) -> GetPalletOptionsResponse {
     let templatecheck = &request.template;
     let pallets = &request.pallets;
+    let sdk_version = request.sdk_version.unwrap_or_default();
+    // Only pallets released for the chosen polkadot-sdk version are offered
+    let pallet_configs = &pallet_configs
+        .iter()
+        .filter(|(_, pallet)| pallet.metadata.sdk_versions.contains(&sdk_version))
+        .map(|(name, pallet)| (name.clone(), pallet.clone()))
+        .collect::<HashMap<_, _>>();
     // Check if the pallets are supported
     for pallet_name in pallets.iter() {
         if !pallet_configs.contains_key(pallet_name) {
             return GetPalletOptionsResponse::PalletNotFound(PlainText(format!(
-                "Pallet {} not found",
-                pallet_name
+                "Pallet {} not found for {}",
+                pallet_name, sdk_version
             )));
         }
     }
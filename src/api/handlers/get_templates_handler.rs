@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use poem_openapi::{param::Path, payload::Json, ApiResponse, Object};
 
-use crate::services::code_generator::types::{PalletConfig, TemplateType};
+use crate::services::code_generator::types::{PalletConfig, SdkVersion, TemplateType};
 
 // Pallet structure that will be returned as JSON
 #[derive(PartialEq, Eq, Debug, Object)]
@@ -72,7 +72,9 @@ pub enum GetTemplatesResponse {
 pub async fn get_templates_handler(
     pallet_configs: &HashMap<String, PalletConfig>,
     query_template_type: Path<TemplateType>,
+    sdk_version: SdkVersion,
     supported_templates: Vec<TemplateType>,
+    template_sdk_versions: &HashMap<TemplateType, Vec<SdkVersion>>,
 ) -> GetTemplatesResponse {
     if !supported_templates.contains(&query_template_type.0) {
         return GetTemplatesResponse::NotFound(Json(format!(
@@ -80,6 +82,21 @@ pub async fn get_templates_handler(
             &query_template_type.0
         )));
     }
+    if !template_sdk_versions
+        .get(&query_template_type.0)
+        .is_some_and(|sdk_versions| sdk_versions.contains(&sdk_version))
+    {
+        return GetTemplatesResponse::NotFound(Json(format!(
+            "Template {:?} is not available for {}.",
+            &query_template_type.0, sdk_version
+        )));
+    }
+    // Only pallets released for the chosen polkadot-sdk version are offered
+    let pallet_configs = &pallet_configs
+        .iter()
+        .filter(|(_, pallet)| pallet.metadata.sdk_versions.contains(&sdk_version))
+        .map(|(name, pallet)| (name.clone(), pallet.clone()))
+        .collect::<HashMap<_, _>>();
     let blockchain_supported_pallets: Vec<Pallet> = pallet_configs
         .iter()
         .filter(|(_, pallet)| {
@@ -3,3 +3,5 @@ pub mod get_dependencies_handler;
 pub mod get_pallet_options_handler;
 pub mod get_status_handler;
 pub mod get_templates_handler;
+pub mod preview_project_handler;
+pub mod reload_catalog_handler;
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use poem_openapi::{
+    payload::{Json, PlainText},
+    ApiResponse, Object,
+};
+
+use super::generate_project_handler::{NewProject, ProjectError};
+use crate::services::code_generator::{
+    types::{UnbuildableSelection, UnprocessableProject},
+    CodeGenerator, CodeGeneratorServiceError, ProjectOptions,
+};
+
+/// A templated file as it would be written into the project archive.
+#[derive(Debug, PartialEq, Eq, Object)]
+pub struct RenderedFile {
+    pub path: String,
+    pub content: String,
+}
+
+#[derive(ApiResponse)]
+pub enum PreviewProjectResponse {
+    /// Returns the templated files of the project, sorted by path.
+    #[oai(status = 200)]
+    Ok(Json<Vec<RenderedFile>>),
+    #[oai(status = 400)]
+    BadRequest(PlainText<String>),
+    #[oai(status = 404)]
+    PalletNotFound(PlainText<String>),
+    /// Lists every parameter configuration that does not fit its pallet, or every rendered file
+    /// that does not parse with the pallet that broke it.
+    #[oai(status = 422)]
+    Unprocessable(Json<UnprocessableProject>),
+    /// Lists every selected or required pallet that the template does not support, or every
+    /// pair of them that cannot be used together.
+    #[oai(status = 409)]
+    UnbuildableSelection(Json<UnbuildableSelection>),
+    #[oai(status = 500)]
+    InternalServerError(PlainText<String>),
+}
+
+impl From<CodeGeneratorServiceError> for PreviewProjectResponse {
+    fn from(e: CodeGeneratorServiceError) -> Self {
+        match ProjectError::from(e) {
+            ProjectError::BadRequest(message) => {
+                PreviewProjectResponse::BadRequest(PlainText(message))
+            }
+            ProjectError::PalletNotFound(message) => {
+                PreviewProjectResponse::PalletNotFound(PlainText(message))
+            }
+            ProjectError::Unprocessable(unprocessable) => {
+                PreviewProjectResponse::Unprocessable(Json(unprocessable))
+            }
+            ProjectError::UnbuildableSelection(unbuildable) => {
+                PreviewProjectResponse::UnbuildableSelection(Json(unbuildable))
+            }
+            ProjectError::InternalServerError(message) => {
+                PreviewProjectResponse::InternalServerError(PlainText(message))
+            }
+        }
+    }
+}
+
+pub async fn preview_project_handler(
+    code_generator_service: Arc<dyn CodeGenerator>,
+    project: Json<NewProject>,
+) -> PreviewProjectResponse {
+    match code_generator_service
+        .preview_project(
+            &project.pallets,
+            &project.template,
+            &ProjectOptions::from(&project.0),
+        )
+        .await
+    {
+        Ok(files) => PreviewProjectResponse::Ok(Json(
+            files
+                .into_iter()
+                .map(|(path, content)| RenderedFile {
+                    path: path.display().to_string(),
+                    content: String::from_utf8_lossy(&content).into_owned(),
+                })
+                .collect(),
+        )),
+        Err(e) => e.into(),
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use poem_openapi::{
+    auth::ApiKey,
+    payload::{Json, PlainText},
+    ApiResponse, Object, SecurityScheme,
+};
+
+use subtle::ConstantTimeEq;
+
+use crate::services::code_generator::{types::TemplateType, CodeGenerator};
+
+/// Token of the admin endpoints, compared with the `ADMIN_TOKEN` environment variable.
+#[derive(SecurityScheme)]
+#[oai(ty = "api_key", key_name = "X-Admin-Token", key_in = "header")]
+pub struct AdminToken(ApiKey);
+
+#[derive(Object)]
+pub struct ReloadedCatalog {
+    pub pallets: u64,
+    pub templates: Vec<TemplateType>,
+}
+
+#[derive(ApiResponse)]
+pub enum ReloadCatalogResponse {
+    /// Returns the catalog now used by new generations.
+    #[oai(status = 200)]
+    Ok(Json<ReloadedCatalog>),
+    /// Returns when the token is wrong or `ADMIN_TOKEN` is not set.
+    #[oai(status = 401)]
+    Unauthorized(PlainText<String>),
+    /// Returns when the new catalog is invalid, the previous one stays in use.
+    #[oai(status = 422)]
+    InvalidCatalog(PlainText<String>),
+}
+
+pub async fn reload_catalog_handler(
+    code_generator_service: Arc<dyn CodeGenerator>,
+    token: AdminToken,
+) -> ReloadCatalogResponse {
+    if !std::env::var("ADMIN_TOKEN").is_ok_and(|admin_token| {
+        !admin_token.is_empty() && bool::from(admin_token.as_bytes().ct_eq(token.0.key.as_bytes()))
+    }) {
+        return ReloadCatalogResponse::Unauthorized(PlainText("Invalid admin token".to_string()));
+    }
+    match code_generator_service.reload_catalog().await {
+        Ok(catalog) => {
+            let mut templates = catalog.templates.clone();
+            templates.sort_by_key(ToString::to_string);
+            ReloadCatalogResponse::Ok(Json(ReloadedCatalog {
+                pallets: catalog.pallet_configs.len() as u64,
+                templates,
+            }))
+        }
+        Err(e) => ReloadCatalogResponse::InvalidCatalog(PlainText(e.to_string())),
+    }
+}
@@ -1,13 +1,21 @@
 use std::sync::Arc;
 
 use crate::services::{
-    code_generator::{types::TemplateType, CodeGenerator, CodeGeneratorServiceError},
+    code_generator::{
+        types::{SdkVersion, TemplateType},
+        CodeGenerator, CodeGeneratorServiceError,
+    },
     traits::{object_store::ObjectStoreService, version_control::VersionControlService},
 };
 use handlers::{
     get_pallet_options_handler::PalletOptionsRequest, get_status_handler::GetStatusResponse,
+    reload_catalog_handler::AdminToken,
+};
+use poem_openapi::{
+    param::{Path, Query},
+    payload::Json,
+    OpenApi,
 };
-use poem_openapi::{param::Path, payload::Json, OpenApi};
 use prometheus::{opts, register_int_counter_vec_with_registry, IntCounterVec, Registry};
 use scc::HashMap as ConcurrentHashMap;
 use uuid::Uuid;
@@ -99,18 +107,34 @@ impl Api {
         )
         .await
     }
+    /// Renders the templated files of a project without archiving or uploading them.
+    #[oai(path = "/preview-project", method = "post")]
+    pub async fn preview_project(
+        &self,
+        project: Json<handlers::generate_project_handler::NewProject>,
+    ) -> handlers::preview_project_handler::PreviewProjectResponse {
+        handlers::preview_project_handler::preview_project_handler(
+            self.code_generator_service.clone(),
+            project,
+        )
+        .await
+    }
     #[oai(path = "/get-templates/:template_type", method = "get")]
     pub async fn get_templates(
         &self,
         template_type: Path<TemplateType>,
+        sdk_version: Query<Option<SdkVersion>>,
     ) -> handlers::get_templates_handler::GetTemplatesResponse {
         self.get_template_counter
             .with_label_values(&[&template_type.0.to_string()])
             .inc();
+        let catalog = self.code_generator_service.catalog();
         handlers::get_templates_handler::get_templates_handler(
-            self.code_generator_service.pallet_configs(),
+            &catalog.pallet_configs,
             template_type,
-            self.code_generator_service.templates().clone(),
+            sdk_version.0.unwrap_or_default(),
+            catalog.templates.clone(),
+            &catalog.template_sdk_versions,
         )
         .await
     }
@@ -120,7 +144,7 @@ impl Api {
         request: Json<PalletOptionsRequest>,
     ) -> handlers::get_pallet_options_handler::GetPalletOptionsResponse {
         handlers::get_pallet_options_handler::get_pallet_options_handler(
-            self.code_generator_service.pallet_configs(),
+            &self.code_generator_service.catalog().pallet_configs,
             request,
         )
         .await
@@ -144,15 +168,30 @@ impl Api {
             }
         }
     }
+    /// Resolves the pallets each requested pallet brings into a project. Breaking change: the
+    /// lists hold `PalletInclusion` objects with a reason instead of pallet names.
     #[oai(path = "/get-dependencies", method = "post")]
     pub async fn get_dependencies(
         &self,
         request: Json<handlers::get_dependencies_handler::GetDependenciesRequest>,
     ) -> handlers::get_dependencies_handler::GetDependenciesResponse {
         handlers::get_dependencies_handler::get_dependencies_handler(
-            self.code_generator_service.pallet_configs(),
+            &self.code_generator_service.catalog().pallet_configs,
             request,
         )
         .await
     }
+    /// Reloads the pallet configs and templates from disk. Generations already running keep the
+    /// catalog they started with, including the template files they render.
+    #[oai(path = "/admin/reload-catalog", method = "post")]
+    pub async fn reload_catalog(
+        &self,
+        token: AdminToken,
+    ) -> handlers::reload_catalog_handler::ReloadCatalogResponse {
+        handlers::reload_catalog_handler::reload_catalog_handler(
+            self.code_generator_service.clone(),
+            token,
+        )
+        .await
+    }
 }
@@ -0,0 +1,40 @@
+//! Checks the pallet configs, e.g. `cargo run --bin lint-configs -- configs`.
+//!
+//! The directory defaults to `CONFIG_DIRECTORY`. Exits with 1 when a problem is found, warnings
+//! are printed but do not fail the lint.
+
+use std::{path::PathBuf, process::ExitCode};
+
+use substrate_runtime_builder::services::code_generator::lint_configs::lint_configs;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenv::from_filename(".env.local").ok();
+    let Some(config_directory) = std::env::args()
+        .nth(1)
+        .or_else(|| std::env::var("CONFIG_DIRECTORY").ok())
+    else {
+        eprintln!("Usage: lint-configs <config directory>");
+        return ExitCode::FAILURE;
+    };
+    match lint_configs(&PathBuf::from(&config_directory)).await {
+        Ok(report) => {
+            for warning in &report.warnings {
+                println!("warning: {}", warning);
+            }
+            if report.problems.is_empty() {
+                println!("{}: no problems found", config_directory);
+                return ExitCode::SUCCESS;
+            }
+            for problem in &report.problems {
+                println!("{}", problem);
+            }
+            println!("{} problem(s) found", report.problems.len());
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -136,8 +136,9 @@ impl ArchiverService for AsyncZipArchiverService {
                 // copy contents into buffer
                 reader.read_to_end_checked(&mut file_buffer).await.unwrap();
 
-                // write buffer into file
+                // write buffer into file, tokio only finishes the write on flush
                 file.write_all(&file_buffer).await.unwrap();
+                file.flush().await.unwrap();
             }
         }
 
@@ -0,0 +1,219 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use poem_openapi::{Enum, Object};
+use serde::Serialize;
+use thiserror::Error;
+
+use super::types::{PalletConfig, TemplateType};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum DependencyResolutionError {
+    #[error("Pallet not found: {0}")]
+    PalletNotFound(String),
+}
+
+/// Why a pallet is part of a project.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum InclusionReason {
+    Requested,
+    Essential,
+    Required,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Object)]
+pub struct PalletInclusion {
+    pub name: String,
+    pub reason: InclusionReason,
+    /// The pallet that pulled this one in, set when `reason` is `required`
+    pub required_by: Option<String>,
+}
+
+/// Computes the transitive closure of the requested pallets, the essential pallets of the
+/// template and everything they require. Each pallet is listed once, with the first reason
+/// it was reached by; mutually required pallets are followed only once.
+pub fn resolve_pallets(
+    catalog: &HashMap<String, PalletConfig>,
+    requested: &[String],
+    template_type: &TemplateType,
+) -> Result<Vec<PalletInclusion>, DependencyResolutionError> {
+    let essential = catalog
+        .values()
+        .filter(|pallet| {
+            pallet
+                .metadata
+                .is_essential
+                .as_ref()
+                .is_some_and(|templates| templates.contains(template_type))
+        })
+        .map(|pallet| pallet.name.clone())
+        .collect::<BTreeSet<_>>();
+    let mut queue = requested
+        .iter()
+        .map(|name| (name.clone(), InclusionReason::Requested, None))
+        .chain(
+            essential
+                .into_iter()
+                .map(|name| (name, InclusionReason::Essential, None)),
+        )
+        .collect::<VecDeque<_>>();
+    let mut seen = HashSet::new();
+    let mut included = vec![];
+    while let Some((name, reason, required_by)) = queue.pop_front() {
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        let pallet = catalog
+            .get(&name)
+            .ok_or_else(|| DependencyResolutionError::PalletNotFound(name.clone()))?;
+        queue.extend(
+            pallet
+                .dependencies
+                .required_pallets
+                .iter()
+                .flatten()
+                .map(|required| {
+                    (
+                        required.clone(),
+                        InclusionReason::Required,
+                        Some(name.clone()),
+                    )
+                }),
+        );
+        included.push(PalletInclusion {
+            name,
+            reason,
+            required_by,
+        });
+    }
+    Ok(included)
+}
+
+/// Names in `required_pallets` that are not in the catalog, as `(pallet, required pallet)`.
+pub fn unknown_required_pallets(catalog: &HashMap<String, PalletConfig>) -> Vec<(String, String)> {
+    let mut unknown = catalog
+        .values()
+        .flat_map(|pallet| {
+            pallet
+                .dependencies
+                .required_pallets
+                .iter()
+                .flatten()
+                .filter(|required| !catalog.contains_key(*required))
+                .map(|required| (pallet.name.clone(), required.clone()))
+        })
+        .collect::<Vec<_>>();
+    unknown.sort();
+    unknown
+}
+
+/// Cycles in the `required_pallets` graph, each as the path that returns to its first pallet.
+pub fn dependency_cycles(catalog: &HashMap<String, PalletConfig>) -> Vec<Vec<String>> {
+    fn visit<'a>(
+        catalog: &'a HashMap<String, PalletConfig>,
+        name: &'a str,
+        path: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        if let Some(start) = path.iter().position(|visiting| *visiting == name) {
+            let mut cycle = path[start..]
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            cycle.push(name.to_string());
+            cycles.push(cycle);
+            return;
+        }
+        let Some(pallet) = catalog.get(name) else {
+            return;
+        };
+        if done.contains(name) {
+            return;
+        }
+        path.push(name);
+        for required in pallet.dependencies.required_pallets.iter().flatten() {
+            visit(catalog, required, path, done, cycles);
+        }
+        path.pop();
+        done.insert(name);
+    }
+
+    let mut names = catalog.keys().map(String::as_str).collect::<Vec<_>>();
+    names.sort();
+    let mut done = HashSet::new();
+    let mut cycles = vec![];
+    for name in names {
+        visit(catalog, name, &mut vec![], &mut done, &mut cycles);
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::code_generator::test_utils::catalog;
+    #[tokio::test]
+    async fn test_resolve_pallets() {
+        let catalog = catalog().await;
+        let mut catalog = catalog.pallet_configs.clone();
+        assert!(unknown_required_pallets(&catalog).is_empty());
+        // Tips -> Treasury -> Assets, without Tips listing Assets itself
+        catalog
+            .get_mut("Pallet Tips")
+            .unwrap()
+            .dependencies
+            .required_pallets = Some(vec!["Pallet Treasury".to_string()]);
+        let included = resolve_pallets(
+            &catalog,
+            &["Pallet Tips".to_string()],
+            &TemplateType::SoloChain,
+        )
+        .unwrap();
+        let reason = |name: &str| {
+            included
+                .iter()
+                .find(|inclusion| inclusion.name == name)
+                .map(|inclusion| (inclusion.reason, inclusion.required_by.clone()))
+        };
+        assert_eq!(
+            reason("Pallet Tips"),
+            Some((InclusionReason::Requested, None))
+        );
+        assert_eq!(
+            reason("Pallet Assets"),
+            Some((
+                InclusionReason::Required,
+                Some("Pallet Treasury".to_string())
+            ))
+        );
+        assert_eq!(reason("Pallet Membership"), None);
+
+        // Referenda and Conviction Voting require each other
+        assert!(dependency_cycles(&catalog)
+            .iter()
+            .any(|cycle| cycle.contains(&"Pallet Referenda".to_string())));
+        let included = resolve_pallets(
+            &catalog,
+            &["Pallet Referenda".to_string()],
+            &TemplateType::SoloChain,
+        )
+        .unwrap();
+        assert_eq!(
+            included
+                .iter()
+                .filter(|inclusion| inclusion.name == "Pallet Referenda")
+                .count(),
+            1
+        );
+        assert!(matches!(
+            resolve_pallets(
+                &catalog,
+                &["Pallet Nope".to_string()],
+                &TemplateType::SoloChain
+            ),
+            Err(DependencyResolutionError::PalletNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use super::types::{InvalidGeneratedFile, PalletConfig};
+
+/// Parses every rendered `.rs` file with `syn` and every `Cargo.toml` with `toml`, so a
+/// broken config snippet is reported before the archive is shipped. The `.rs` files that parse
+/// are pretty-printed with `prettyplease`. Other files are skipped.
+pub fn check_generated_files(
+    files: &mut [(PathBuf, Vec<u8>)],
+    pallet_configs: &[PalletConfig],
+) -> Vec<InvalidGeneratedFile> {
+    files
+        .iter_mut()
+        .filter_map(|(path, content)| check_generated_file(path, content, pallet_configs))
+        .collect()
+}
+
+fn check_generated_file(
+    path: &Path,
+    content: &mut Vec<u8>,
+    pallet_configs: &[PalletConfig],
+) -> Option<InvalidGeneratedFile> {
+    let is_rust = is_rust_file(path);
+    let is_manifest = path.file_name().is_some_and(|name| name == "Cargo.toml");
+    if !is_rust && !is_manifest {
+        return None;
+    }
+    let invalid = |line: usize, message: String, failing_line: &str| InvalidGeneratedFile {
+        file: path.display().to_string(),
+        line: line as u32,
+        message,
+        pallet: contributing_pallet(failing_line, pallet_configs),
+    };
+    let text = match std::str::from_utf8(content) {
+        Ok(text) => text,
+        Err(e) => return Some(invalid(1, e.to_string(), "")),
+    };
+    let (line, message) = if is_rust {
+        match syn::parse_file(text) {
+            Ok(file) => {
+                *content = pretty_print(text, &file).into_bytes();
+                return None;
+            }
+            Err(e) => (e.span().start().line, e.to_string()),
+        }
+    } else {
+        let e = toml::from_str::<toml::Table>(text).err()?;
+        let line = e
+            .span()
+            .map_or(1, |span| text[..span.start].matches('\n').count() + 1);
+        (line, e.message().to_string())
+    };
+    let failing_line = text.lines().nth(line.saturating_sub(1)).unwrap_or("");
+    Some(invalid(line, message, failing_line))
+}
+
+/// `syn` keeps doc comments only, so the leading `//` block of the file, such as the license
+/// header, is carried over by hand.
+fn pretty_print(text: &str, file: &syn::File) -> String {
+    let header = text
+        .lines()
+        .take_while(|line| {
+            let line = line.trim_start();
+            line.is_empty()
+                || (line.starts_with("//") && !line.starts_with("///") && !line.starts_with("//!"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let formatted = prettyplease::unparse(file);
+    match header.trim() {
+        "" => formatted,
+        header => format!("{}\n\n{}", header, formatted),
+    }
+}
+
+fn is_rust_file(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "rs")
+}
+
+/// Rendered config values are copied verbatim, so the pallet with the longest value found in
+/// the failing line is the one that contributed it. Ties between pallets stay unattributed.
+fn contributing_pallet(failing_line: &str, pallet_configs: &[PalletConfig]) -> Option<String> {
+    let mut best: Option<(usize, &str)> = None;
+    let mut tie = false;
+    for pallet in pallet_configs {
+        let Ok(config) = serde_json::to_value(pallet) else {
+            continue;
+        };
+        let mut values = vec![];
+        collect_strings(&config, &mut values);
+        let Some(length) = values
+            .iter()
+            .flat_map(|value| value.lines())
+            .map(str::trim)
+            .filter(|value| value.len() >= 4 && failing_line.contains(value))
+            .map(str::len)
+            .max()
+        else {
+            continue;
+        };
+        match best {
+            Some((best_length, _)) if best_length > length => {}
+            Some((best_length, _)) if best_length == length => tie = true,
+            _ => {
+                best = Some((length, &pallet.name));
+                tie = false;
+            }
+        }
+    }
+    best.filter(|_| !tie).map(|(_, name)| name.to_string())
+}
+
+fn collect_strings<'a>(value: &'a Value, strings: &mut Vec<&'a str>) {
+    match value {
+        Value::String(string) => strings.push(string),
+        Value::Array(values) => values
+            .iter()
+            .for_each(|value| collect_strings(value, strings)),
+        Value::Object(values) => values
+            .values()
+            .for_each(|value| collect_strings(value, strings)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    use crate::services::code_generator::{
+        test_utils::{catalog, code_generator},
+        types::TemplateType,
+        CodeGenerator,
+    };
+    #[tokio::test]
+    async fn test_invalid_manifest() {
+        let catalog = catalog().await;
+        let manifest = b"[package]\nname = \"pallet-nfts\nversion = \"1.0.0\"\n".to_vec();
+        let invalid_files = check_generated_files(
+            &mut [(PathBuf::from("runtime/Cargo.toml"), manifest)],
+            &[catalog.pallet_configs["Pallet Nfts"].clone()],
+        );
+        assert_eq!(invalid_files[0].line, 2);
+        assert_eq!(invalid_files[0].pallet.as_deref(), Some("Pallet Nfts"));
+    }
+
+    #[test]
+    fn test_pretty_print_keeps_header() {
+        let mut files = [(
+            PathBuf::from("runtime/src/apis.rs"),
+            b"// License\n// text\n\nuse  a::b ;\n// dropped\nfn  f ( ) {}\n".to_vec(),
+        )];
+        assert!(check_generated_files(&mut files, &[]).is_empty());
+        assert_eq!(
+            String::from_utf8(files[0].1.clone()).unwrap(),
+            "// License\n// text\n\nuse a::b;\nfn f() {}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generated_files_formatted() {
+        let cg = code_generator().await;
+        let pallets = ["Pallet Nfts", "Pallet Democracy", "Pallet Assets"]
+            .into_iter()
+            .map(|name| (name.to_string(), None))
+            .collect::<HashMap<_, _>>();
+        let archive = cg
+            .generate_project_archive(&pallets, &TemplateType::SoloChain, &Default::default())
+            .await
+            .unwrap();
+        let output = tmpdir::TmpDir::new("formatted").await.unwrap();
+        cg.unpack_archive_to_folder(archive, &output.to_path_buf())
+            .await
+            .unwrap();
+
+        let runtime_lib =
+            std::fs::read_to_string(output.to_path_buf().join("runtime/src/lib.rs")).unwrap();
+        assert!(!runtime_lib.contains("\n\t"));
+        // formatting is stable, so a second generation does not reformat anything
+        let file = syn::parse_file(&runtime_lib).unwrap();
+        assert_eq!(pretty_print(&runtime_lib, &file), runtime_lib);
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+
+use super::{
+    dependencies::{dependency_cycles, unknown_required_pallets},
+    load_configs::{parse_config, read_config_files, LoadConfigsError},
+    types::PalletConfig,
+};
+
+/// Types defined by every template runtime or generated by `construct_runtime`.
+pub const RUNTIME_TYPES: [&str; 16] = [
+    "AccountId",
+    "Balance",
+    "Block",
+    "BlockNumber",
+    "Hash",
+    "Nonce",
+    "Runtime",
+    "RuntimeCall",
+    "RuntimeEvent",
+    "RuntimeError",
+    "RuntimeOrigin",
+    "RuntimeFreezeReason",
+    "RuntimeHoldReason",
+    "RuntimeSlashReason",
+    "RuntimeLockId",
+    "RuntimeTask",
+];
+
+/// Findings of `lint_configs`, prefixed with the file they were found in.
+#[derive(Debug, Default)]
+pub struct LintReport {
+    /// Mistakes that make a catalog unusable
+    pub problems: Vec<String>,
+    /// Legal but likely unintended, such as mutually required pallets
+    pub warnings: Vec<String>,
+}
+
+/// Checks every config of the directory, including the references between them. Only IO
+/// errors abort the lint.
+pub async fn lint_configs(path: &Path) -> Result<LintReport, LoadConfigsError> {
+    let mut problems = vec![];
+    let mut catalog = HashMap::new();
+    let mut files = HashMap::new();
+    for (file_path, content) in read_config_files(path).await? {
+        let file = file_path.display().to_string();
+        let pallet_config = match parse_config(&file_path, &content) {
+            Ok(pallet_config) => pallet_config,
+            Err(e @ LoadConfigsError::ParseError(..)) => {
+                problems.push(e.to_string());
+                continue;
+            }
+            Err(e) => {
+                problems.push(format!("{}: {}", file, e));
+                continue;
+            }
+        };
+        if let Some(other) = files.get(&pallet_config.name) {
+            problems.push(format!(
+                "{}: pallet {} is already defined by {}",
+                file, pallet_config.name, other
+            ));
+            continue;
+        }
+        files.insert(pallet_config.name.clone(), file);
+        catalog.insert(pallet_config.name.clone(), pallet_config);
+    }
+    problems.extend(
+        config_reference_errors(&catalog)
+            .into_iter()
+            .map(|(pallet, problem)| format!("{}: {}", files[&pallet], problem)),
+    );
+    let warnings = config_reference_warnings(&catalog)
+        .into_iter()
+        .map(|(pallet, warning)| format!("{}: {}", files[&pallet], warning))
+        .collect();
+    Ok(LintReport { problems, warnings })
+}
+
+/// References that generate fine but are likely unintended, as `(pallet, warning)`.
+pub fn config_reference_warnings(catalog: &HashMap<String, PalletConfig>) -> Vec<(String, String)> {
+    dependency_cycles(catalog)
+        .into_iter()
+        .map(|cycle| {
+            (
+                cycle[0].clone(),
+                format!("required pallets form a cycle: {}", cycle.join(" -> ")),
+            )
+        })
+        .collect()
+}
+
+/// References that only fail once a project is generated, as `(pallet, problem)`.
+pub fn config_reference_errors(catalog: &HashMap<String, PalletConfig>) -> Vec<(String, String)> {
+    let mut errors = unknown_required_pallets(catalog)
+        .into_iter()
+        .map(|(pallet, required)| {
+            (
+                pallet,
+                format!("required pallet {} does not exist", required),
+            )
+        })
+        .collect::<Vec<_>>();
+    let runtime_names = catalog
+        .values()
+        .filter_map(|pallet| pallet.runtime.construct_runtime.runtime.first())
+        .collect::<BTreeSet<_>>();
+    for pallet in catalog.values() {
+        let mut error = |problem: String| errors.push((pallet.name.clone(), problem));
+        let runtime = &pallet.runtime;
+        let referenced_pallets = pallet
+            .dependencies
+            .conflicts_with
+            .iter()
+            .flatten()
+            .map(|name| ("conflicting", name))
+            .chain(
+                runtime
+                    .when
+                    .iter()
+                    .flat_map(|when| when.keys())
+                    .map(|name| ("conditional", name)),
+            )
+            .chain(
+                runtime
+                    .pallet_trait_alternatives
+                    .iter()
+                    .flat_map(|alternatives| alternatives.values().flatten())
+                    .flat_map(|alternative| alternative.required_pallets.iter().flatten())
+                    .map(|name| ("alternative", name)),
+            );
+        for (kind, name) in referenced_pallets {
+            if !catalog.contains_key(name) {
+                error(format!("{} pallet {} does not exist", kind, name));
+            }
+        }
+
+        let parameter_types = runtime
+            .optional_parameter_types
+            .iter()
+            .flat_map(|parameter_types| parameter_types.values())
+            .map(|parameter_type| parameter_type.name.as_str())
+            .collect::<BTreeSet<_>>();
+        let additional_code = runtime
+            .additional_pallet_impl_code
+            .iter()
+            .chain(runtime.additional_runtime_lib_code.iter().flatten())
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let is_defined = |identifier: &str| {
+            parameter_types.contains(identifier)
+                || RUNTIME_TYPES.contains(&identifier)
+                || runtime_names.contains(&identifier.to_string())
+                || additional_code
+                    .iter()
+                    .any(|code| contains_identifier(code, identifier))
+        };
+        // By convention a trait set to its own name refers to a parameter type
+        let trait_values = runtime
+            .pallet_traits
+            .iter()
+            .chain(
+                runtime
+                    .when
+                    .iter()
+                    .flat_map(|when| when.values())
+                    .flat_map(|conditional| conditional.pallet_traits.iter().flatten()),
+            )
+            .map(|(name, value)| (name, value.as_str()))
+            .chain(runtime.pallet_trait_alternatives.iter().flatten().flat_map(
+                |(name, alternatives)| {
+                    alternatives
+                        .iter()
+                        .map(move |alternative| (name, alternative.value.as_str()))
+                },
+            ));
+        for (name, value) in trait_values {
+            if name == value && !is_defined(value) {
+                error(format!(
+                    "trait {} points at parameter type {}, which is not declared",
+                    name, value
+                ));
+            }
+        }
+    }
+    errors.sort();
+    errors.dedup();
+    errors
+}
+
+fn contains_identifier(code: &str, identifier: &str) -> bool {
+    code.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .any(|word| word == identifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::code_generator::test_utils::catalog;
+    #[tokio::test]
+    async fn test_lint_configs() {
+        dotenv::from_filename(".env.local").ok();
+        let config_directory = std::env::var("CONFIG_DIRECTORY").unwrap();
+        let report = lint_configs(Path::new(&config_directory)).await.unwrap();
+        assert_eq!(report.problems, Vec::<String>::new());
+        assert_eq!(
+            report.warnings,
+            vec![format!(
+                "{}: required pallets form a cycle: \
+                 Pallet Conviction Voting -> Pallet Referenda -> Pallet Conviction Voting",
+                Path::new(&config_directory)
+                    .join("pallet_conviction_voting.toml")
+                    .display()
+            )]
+        );
+
+        let mut catalog = catalog().await.pallet_configs.clone();
+        let democracy = catalog.get_mut("Pallet Democracy").unwrap();
+        democracy.runtime.optional_parameter_types = None;
+        democracy.dependencies.conflicts_with = Some(vec!["Pallet Nope".to_string()]);
+        catalog
+            .get_mut("Pallet Preimage")
+            .unwrap()
+            .dependencies
+            .required_pallets = Some(vec!["Pallet Democracy".to_string()]);
+        catalog
+            .get_mut("Pallet Democracy")
+            .unwrap()
+            .dependencies
+            .required_pallets = Some(vec!["Pallet Preimage".to_string()]);
+        assert!(config_reference_warnings(&catalog)
+            .iter()
+            .any(|(_, warning)| warning.contains("Pallet Democracy -> Pallet Preimage")));
+        let errors = config_reference_errors(&catalog);
+        assert!(errors.contains(&(
+            "Pallet Democracy".to_string(),
+            "conflicting pallet Pallet Nope does not exist".to_string()
+        )));
+        assert!(errors.contains(&(
+            "Pallet Democracy".to_string(),
+            "trait CooloffPeriod points at parameter type CooloffPeriod, which is not declared"
+                .to_string()
+        )));
+    }
+}
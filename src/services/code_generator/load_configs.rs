@@ -1,6 +1,13 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
-use super::types::PalletConfig;
+use super::{
+    dependencies::{dependency_cycles, unknown_required_pallets},
+    types::PalletConfig,
+};
+use log::warn;
 use thiserror::Error;
 
 // Define the LoadConfigsError
@@ -8,8 +15,19 @@ use thiserror::Error;
 pub enum LoadConfigsError {
     #[error("{0}")]
     FileIOError(String),
-    #[error("{0}")]
-    TomlError(#[from] toml::de::Error),
+    /// Location as `file:line`, and the reason
+    #[error("{0}: {1}")]
+    ParseError(String, String),
+    #[error("Invalid dependency in {0}: {1}")]
+    InvalidDependencyError(String, String),
+    #[error("Invalid parameter type in {0}: {1}")]
+    InvalidParameterTypeError(String, String),
+    #[error("Invalid metadata in {0}: {1}")]
+    InvalidMetadataError(String, String),
+    #[error("{0}: pallet {1} is already defined by another config")]
+    DuplicatePalletError(String, String),
+    #[error("Unknown required pallet in {0}: {1}")]
+    UnknownRequiredPalletError(String, String),
 }
 
 impl From<std::io::Error> for LoadConfigsError {
@@ -18,38 +36,158 @@ impl From<std::io::Error> for LoadConfigsError {
     }
 }
 
+pub fn validate_dependencies(pallet_config: &PalletConfig) -> Result<(), LoadConfigsError> {
+    let dependencies = &pallet_config.dependencies;
+    let node_rpc_dependencies = pallet_config
+        .node_rpc
+        .iter()
+        .flat_map(|node_rpc| node_rpc.dependencies.iter().flatten());
+    std::iter::once(&dependencies.pallet)
+        .chain(dependencies.additional_pallets.iter().flatten())
+        .chain(node_rpc_dependencies)
+        .try_for_each(|dependency| {
+            dependency.validate().map_err(|e| {
+                LoadConfigsError::InvalidDependencyError(pallet_config.name.clone(), e)
+            })
+        })
+}
+
+/// Checks the metadata values that the schema alone cannot, such as the `updated` timestamp.
+pub fn validate_metadata(pallet_config: &PalletConfig) -> Result<(), LoadConfigsError> {
+    if let Some(updated) = &pallet_config.metadata.updated {
+        if updated.parse::<u64>().is_err() {
+            return Err(LoadConfigsError::InvalidMetadataError(
+                pallet_config.name.clone(),
+                format!("updated {} is not a Unix timestamp", updated),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that every parameter type is either typed or a format expression, with a valid default.
+pub fn validate_parameter_types(pallet_config: &PalletConfig) -> Result<(), LoadConfigsError> {
+    let invalid = |parameter: &str, reason: String| {
+        LoadConfigsError::InvalidParameterTypeError(
+            pallet_config.name.clone(),
+            format!("{}: {}", parameter, reason),
+        )
+    };
+    for parameter_type in pallet_config
+        .runtime
+        .optional_parameter_types
+        .iter()
+        .flat_map(|parameter_types| parameter_types.values())
+    {
+        match (&parameter_type.value, &parameter_type.expression) {
+            (Some(value), None) => value
+                .validate(&parameter_type.p_type, &value.default_value)
+                .map_err(|e| invalid(&parameter_type.name, e))?,
+            (None, Some(_)) => {}
+            _ => {
+                return Err(invalid(
+                    &parameter_type.name,
+                    "exactly one of `value` and `expression` must be set".to_string(),
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses one pallet config, errors name the file and the line.
+pub fn parse_config(path: &Path, content: &str) -> Result<PalletConfig, LoadConfigsError> {
+    let pallet_config = toml::from_str::<PalletConfig>(content).map_err(|e| {
+        let location = match e.span() {
+            Some(span) => format!(
+                "{}:{}",
+                path.display(),
+                content[..span.start].matches('\n').count() + 1
+            ),
+            None => path.display().to_string(),
+        };
+        LoadConfigsError::ParseError(location, e.message().to_string())
+    })?;
+    validate_dependencies(&pallet_config)?;
+    validate_parameter_types(&pallet_config)?;
+    validate_metadata(&pallet_config)?;
+    Ok(pallet_config)
+}
+
+/// Reads the `.toml` files of the config directory, sorted by path.
+pub async fn read_config_files(path: &Path) -> Result<Vec<(PathBuf, String)>, LoadConfigsError> {
+    let mut read_dir = tokio::fs::read_dir(path).await?;
+
+    let mut file_paths = vec![];
+    while let Some(entry) = read_dir.next_entry().await? {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("toml") {
+            file_paths.push(path);
+        }
+    }
+    file_paths.sort();
+    let mut files = vec![];
+    for file_path in file_paths {
+        let content = tokio::fs::read_to_string(&file_path).await?;
+        files.push((file_path, content));
+    }
+    Ok(files)
+}
+
 pub async fn load_configs(
     path: PathBuf,
 ) -> Result<HashMap<String, PalletConfig>, LoadConfigsError> {
-    let mut read_dir = tokio::fs::read_dir(&path).await?;
+    let mut pallet_configs = HashMap::new();
+    for (file_path, content) in read_config_files(&path).await? {
+        let pallet_config = parse_config(&file_path, &content)?;
+        let name = pallet_config.name.clone();
+        if pallet_configs.insert(name.clone(), pallet_config).is_some() {
+            return Err(LoadConfigsError::DuplicatePalletError(
+                file_path.display().to_string(),
+                name,
+            ));
+        }
+    }
+    if let Some((pallet, required)) = unknown_required_pallets(&pallet_configs).into_iter().next() {
+        return Err(LoadConfigsError::UnknownRequiredPalletError(
+            pallet, required,
+        ));
+    }
+    // Mutually required pallets are resolved together, but worth knowing about
+    for cycle in dependency_cycles(&pallet_configs) {
+        warn!("Dependency cycle in pallet configs: {}", cycle.join(" -> "));
+    }
+    Ok(pallet_configs)
+}
 
-    let mut dir_entries = vec![];
-    while let Some(entry) = read_dir.next_entry().await? {
-        dir_entries.push(entry);
-    }
-    // Filter and read .toml files into strings
-    let file_paths = dir_entries
-        .into_iter()
-        // Filter out non-.toml files
-        .filter_map(|entry| {
-            let path = entry.path();
-            if path.extension().and_then(|ext| ext.to_str()) == Some("toml") {
-                Some(path)
-            } else {
-                None
-            }
-        })
-        .collect::<Vec<_>>();
-    let mut toml_strings_no_updated = vec![];
-    for file_path in file_paths {
-        let string = tokio::fs::read_to_string(&file_path).await?;
-        toml_strings_no_updated.push(string);
-    }
-    toml_strings_no_updated
-        .into_iter()
-        .map(|x| match toml::from_str::<PalletConfig>(x.as_str()) {
-            Ok(pallet_config) => Ok((pallet_config.name.clone(), pallet_config)),
-            Err(e) => Err(e.into()),
-        })
-        .collect::<Result<HashMap<String, PalletConfig>, LoadConfigsError>>()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_parse_config_error_location() {
+        dotenv::from_filename(".env.local").ok();
+        let config_directory = std::env::var("CONFIG_DIRECTORY").unwrap();
+        let content =
+            std::fs::read_to_string(Path::new(&config_directory).join("pallet_aura.toml"))
+                .unwrap()
+                .replace("[runtime]\n", "[runtime]\nadditional_code = []\n");
+        let error = parse_config(Path::new("pallet_aura.toml"), &content).unwrap_err();
+        assert!(matches!(error, LoadConfigsError::ParseError(_, _)));
+        assert!(error.to_string().starts_with("pallet_aura.toml:"));
+    }
+
+    #[test]
+    fn test_parse_config_updated() {
+        dotenv::from_filename(".env.local").ok();
+        let config_directory = std::env::var("CONFIG_DIRECTORY").unwrap();
+        let content =
+            std::fs::read_to_string(Path::new(&config_directory).join("pallet_referenda.toml"))
+                .unwrap();
+        assert!(parse_config(Path::new("pallet_referenda.toml"), &content).is_ok());
+        let content = content.replace("updated = \"1729175503\"", "updated = \"yesterday\"");
+        assert!(matches!(
+            parse_config(Path::new("pallet_referenda.toml"), &content),
+            Err(LoadConfigsError::InvalidMetadataError(_, _))
+        ));
+    }
 }
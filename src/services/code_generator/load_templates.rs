@@ -1,9 +1,13 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use thiserror::Error;
 use tokio::fs;
 
-use super::types::TemplateType;
+use super::templating::handle_templates::{runtime_lib::template_runtime_aliases, HBS_SUFFIX};
+use super::types::{SdkVersion, TemplateType};
+
+const SDK_VERSIONS_FILE: &str = "sdk_versions.toml";
 
 // Define the LoadTemplatesError
 #[derive(Error, Debug, Clone)]
@@ -12,6 +16,8 @@ pub enum LoadTemplatesError {
     ReadDirError(String),
     #[error("Invalid template type: {0}")]
     InvalidTemplateType(String),
+    #[error("Failed to parse {SDK_VERSIONS_FILE}: {0}")]
+    SdkVersionsError(String),
 }
 
 impl From<std::io::Error> for LoadTemplatesError {
@@ -46,3 +52,52 @@ pub async fn load_templates(path: PathBuf) -> Result<Vec<TemplateType>, LoadTemp
 
     Ok(implemented_templates)
 }
+
+/// Reads the polkadot-sdk releases supported by each template.
+pub async fn load_template_sdk_versions(
+    path: PathBuf,
+) -> Result<HashMap<TemplateType, Vec<SdkVersion>>, LoadTemplatesError> {
+    let content = fs::read_to_string(path.join(SDK_VERSIONS_FILE)).await?;
+    toml::from_str(&content).map_err(|e| LoadTemplatesError::SdkVersionsError(e.to_string()))
+}
+
+/// Reads every `.hbs` file below `path`, keyed by its path relative to `path`.
+pub async fn load_template_files(
+    path: PathBuf,
+) -> Result<HashMap<PathBuf, String>, LoadTemplatesError> {
+    let mut template_files = HashMap::new();
+    let mut directories = vec![path.clone()];
+    while let Some(directory) = directories.pop() {
+        let mut entries = fs::read_dir(&directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let entry_path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                directories.push(entry_path);
+            } else if entry_path.extension().is_some_and(|ext| ext == HBS_SUFFIX) {
+                let content = fs::read_to_string(&entry_path).await?;
+                let relative_path = entry_path
+                    .strip_prefix(&path)
+                    .map_err(|e| LoadTemplatesError::ReadDirError(e.to_string()))?;
+                template_files.insert(relative_path.to_path_buf(), content);
+            }
+        }
+    }
+    Ok(template_files)
+}
+
+/// Reads the runtime aliases every template declares itself, such as `System`, from the
+/// `.hbs` files loaded by [`load_template_files`].
+pub fn load_template_runtime_aliases(
+    template_files: &HashMap<PathBuf, String>,
+    templates: &[TemplateType],
+) -> Result<HashMap<TemplateType, Vec<String>>, LoadTemplatesError> {
+    let mut runtime_aliases = HashMap::new();
+    for template in templates {
+        let runtime_lib = Path::new(&template.to_string()).join("runtime/src/lib.rs.hbs");
+        let content = template_files.get(&runtime_lib).ok_or_else(|| {
+            LoadTemplatesError::ReadDirError(format!("{} is missing", runtime_lib.display()))
+        })?;
+        runtime_aliases.insert(template.clone(), template_runtime_aliases(content));
+    }
+    Ok(runtime_aliases)
+}
@@ -1,24 +1,67 @@
+pub mod dependencies;
+pub mod generated_files;
+pub mod lint_configs;
 pub mod load_configs;
 pub mod load_templates;
 pub mod service;
 pub mod templating;
+#[cfg(test)]
+mod test_utils;
 pub mod types;
 
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use thiserror::Error;
-use types::{PalletConfig, TemplateType};
+use types::{
+    InvalidGeneratedFile, InvalidParameter, PalletConfig, PalletScaffold, SdkVersion, TemplateType,
+    UnsupportedPallet,
+};
 
 use load_configs::LoadConfigsError;
 use load_templates::LoadTemplatesError;
 
 use crate::{
-    api::handlers::generate_project_handler::ParameterConfiguration,
+    api::handlers::generate_project_handler::{PalletInstance, ParameterConfiguration},
     services::traits::archiver::ArchiverError,
 };
 
 pub type Result<T> = std::result::Result<T, CodeGeneratorServiceError>;
+
+/// Per-project choices applied on top of the pallet catalog.
+#[derive(Clone, Default)]
+pub struct ProjectOptions {
+    /// `construct_runtime` indices keyed by pallet name
+    pub pallet_indices: HashMap<String, u8>,
+    /// Named instances keyed by the name of an instantiable pallet
+    pub pallet_instances: HashMap<String, Vec<PalletInstance>>,
+    /// Pallets that are not part of the catalog
+    pub custom_pallets: Vec<PalletConfig>,
+    /// New pallet crates generated into `pallets/` and added to the runtime
+    pub pallet_scaffolds: Vec<PalletScaffold>,
+    /// Selected `pallet_trait_alternatives` by associated type, keyed by pallet name
+    pub pallet_traits: HashMap<String, HashMap<String, String>>,
+    pub sdk_version: SdkVersion,
+}
+
+/// The pallet configs and templates, loaded from `CONFIG_DIRECTORY` and `TEMPLATES_DIRECTORY`
+/// and swapped as a whole on reload so a generation never mixes two versions of them. The
+/// `.hbs` files are rendered from the contents loaded here, never read again from disk.
+#[derive(Debug, Default)]
+pub struct Catalog {
+    pub pallet_configs: HashMap<String, PalletConfig>,
+    pub templates: Vec<TemplateType>,
+    pub template_sdk_versions: HashMap<TemplateType, Vec<SdkVersion>>,
+    pub template_runtime_aliases: HashMap<TemplateType, Vec<String>>,
+    /// `.hbs` files keyed by their path under `TEMPLATES_DIRECTORY`
+    pub template_files: HashMap<PathBuf, String>,
+    /// `.hbs` files keyed by their path under the pallet scaffold directory
+    pub pallet_scaffold_files: HashMap<PathBuf, String>,
+}
 // Define the CodeGeneratorServiceError
 #[derive(Error, Debug, Clone)]
 pub enum CodeGeneratorServiceError {
@@ -36,16 +79,59 @@ pub enum CodeGeneratorServiceError {
     OtherError(String),
     #[error("Invalid template type: {0}")]
     InvalidTemplateType(String),
+    #[error("Invalid pallet index: {0}")]
+    PalletIndexError(String),
+    #[error("Conflicting dependency versions: {0}")]
+    DependencyConflictError(String),
+    #[error("Unsupported polkadot-sdk release: {0}")]
+    UnsupportedSdkVersion(String),
+    #[error("Invalid pallet instance: {0}")]
+    PalletInstanceError(String),
+    #[error("Invalid custom pallet: {0}")]
+    CustomPalletError(String),
+    #[error("Invalid pallet scaffold: {0}")]
+    PalletScaffoldError(String),
+    #[error("Conflicting pallets: {}", .0.join("; "))]
+    PalletConflictError(Vec<String>),
+    #[error("Invalid pallet trait selection: {0}")]
+    PalletTraitError(String),
+    #[error("Invalid parameter configuration: {}", join_errors(.0))]
+    InvalidParameterConfiguration(Vec<InvalidParameter>),
+    #[error("Unsupported pallets: {}", join_errors(.0))]
+    UnsupportedPalletError(Vec<UnsupportedPallet>),
+    #[error("Generated files do not parse: {}", join_errors(.0))]
+    InvalidGeneratedFileError(Vec<InvalidGeneratedFile>),
+    #[error("Invalid catalog: {}", .0.join("; "))]
+    InvalidCatalogError(Vec<String>),
+}
+
+fn join_errors<T: ToString>(errors: &[T]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 #[async_trait]
 pub trait CodeGenerator: Send + Sync {
-    fn pallet_configs(&self) -> &HashMap<String, PalletConfig>;
-    fn templates(&self) -> &Vec<TemplateType>;
+    /// The current catalog, which stays valid while it is used even if a reload replaces it.
+    fn catalog(&self) -> Arc<Catalog>;
+    /// Loads and validates the catalog again, and replaces the current one only if it is valid.
+    async fn reload_catalog(&self) -> Result<Arc<Catalog>>;
     async fn generate_project_archive(
         &self,
         pallets: &HashMap<String, Option<HashMap<String, ParameterConfiguration>>>,
         template_type: &TemplateType,
+        options: &ProjectOptions,
     ) -> Result<Vec<u8>>;
+    /// Renders the templated files of a project, as `(path, content)` sorted by path, without
+    /// archiving them.
+    async fn preview_project(
+        &self,
+        pallets: &HashMap<String, Option<HashMap<String, ParameterConfiguration>>>,
+        template_type: &TemplateType,
+        options: &ProjectOptions,
+    ) -> Result<Vec<(PathBuf, Vec<u8>)>>;
     async fn unpack_archive_to_folder(&self, buffer: Vec<u8>, output: &Path) -> Result<()>;
 }
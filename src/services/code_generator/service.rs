@@ -1,25 +1,654 @@
-use std::path::Path;
-use std::{collections::HashMap, sync::Arc};
-
-use super::load_configs::load_configs;
-use super::load_templates::load_templates;
-use super::templating::handle_templates::manifest::generate_manifest_file_to_bytes;
-use super::templating::handle_templates::runtime_lib::generate_runtime_lib_file_bytes;
-use super::templating::handle_templates::HBS_SUFFIX;
-use super::types::TemplateType;
-use super::{CodeGeneratorServiceError, Result};
+use std::path::{Path, PathBuf};
+use std::{
+    collections::{hash_map::Entry, BTreeSet, HashMap, HashSet},
+    sync::Arc,
+};
+
+use super::dependencies::{resolve_pallets, DependencyResolutionError};
+use super::generated_files::check_generated_files;
+use super::lint_configs::{config_reference_errors, RUNTIME_TYPES};
+use super::load_configs::{
+    load_configs, validate_dependencies, validate_metadata, validate_parameter_types,
+};
+use super::load_templates::{
+    load_template_files, load_template_runtime_aliases, load_template_sdk_versions, load_templates,
+};
+use super::templating::handle_templates::chain_spec::generate_chain_spec_file_bytes;
+use super::templating::handle_templates::manifest::{
+    dependency_conflicts, generate_manifest_file_to_bytes, generate_node_manifest_file_to_bytes,
+};
+use super::templating::handle_templates::node_rpc::generate_node_rpc_file_bytes;
+use super::templating::handle_templates::pallet_scaffold::{
+    generate_pallet_scaffold_files, generate_workspace_manifest_file_bytes, scaffold_runtime_name,
+    PALLET_SCAFFOLD_DIRECTORY,
+};
+use super::templating::handle_templates::runtime_lib::{
+    generate_runtime_lib_file_bytes, runtime_alias, template_pallet_indices, FIRST_PALLET_INDEX,
+};
+use super::templating::handle_templates::{
+    render_handlebars_template_to_bytes, SdkSources, TemplateRenderError, HBS_SUFFIX,
+};
+use super::types::{
+    CargoComplexDependency, InvalidParameter, PalletBenchmarksConfig, PalletConstructRuntimeConfig,
+    PalletDependencyConfig, PalletMetadata, PalletRuntimeConfig, PalletScaffold, ParameterType,
+    ParameterTypeExpression, ParameterTypePrefix, ParameterValue, ParameterValueKind, SdkVersion,
+    TemplateType, UnsupportedPallet, FRONTIER_REPO, POLKADOT_SDK_REPO,
+};
+use super::{Catalog, CodeGeneratorServiceError, ProjectOptions, Result};
 use crate::api::handlers::generate_project_handler::ParameterConfiguration;
 use crate::services::traits::archiver::ArchiverService;
+use arc_swap::ArcSwap;
 use async_trait::async_trait;
+use log::info;
 
 use super::{types::PalletConfig, CodeGenerator};
 
+/// Validates the configured pallet indices against the ones hard-coded in the
+/// template and assigns the lowest free index to every remaining pallet, in
+/// pallet name order, so the same request always yields the same runtime.
+fn assign_pallet_indices(
+    pallet_configs: &mut [PalletConfig],
+    reserved_indices: &[u8],
+) -> Result<()> {
+    pallet_configs.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut used_indices: HashMap<u8, String> = HashMap::new();
+    for pallet in pallet_configs.iter() {
+        if let Some(index) = pallet.runtime.construct_runtime.pallet_index {
+            if reserved_indices.contains(&index) {
+                return Err(CodeGeneratorServiceError::PalletIndexError(format!(
+                    "index {} of {} is already used by the template",
+                    index, pallet.name
+                )));
+            }
+            if let Some(other) = used_indices.insert(index, pallet.name.clone()) {
+                return Err(CodeGeneratorServiceError::PalletIndexError(format!(
+                    "index {} is assigned to both {} and {}",
+                    index, other, pallet.name
+                )));
+            }
+        }
+    }
+    let mut next_index = FIRST_PALLET_INDEX;
+    for pallet in pallet_configs
+        .iter_mut()
+        .filter(|pallet| pallet.runtime.construct_runtime.pallet_index.is_none())
+    {
+        while reserved_indices.contains(&next_index) || used_indices.contains_key(&next_index) {
+            next_index = next_index.checked_add(1).ok_or_else(|| {
+                CodeGeneratorServiceError::PalletIndexError(format!(
+                    "no free index left for {}",
+                    pallet.name
+                ))
+            })?;
+        }
+        used_indices.insert(next_index, pallet.name.clone());
+        pallet.runtime.construct_runtime.pallet_index = Some(next_index);
+    }
+    Ok(())
+}
+
+/// Points polkadot-sdk dependencies of a pallet at the selected release. The Frontier templates
+/// follow the stable branch of the release, for polkadot-sdk and Frontier crates alike.
+fn pin_sdk_version(
+    pallet: &mut PalletConfig,
+    template_type: &TemplateType,
+    sdk_version: &SdkVersion,
+) {
+    let dependencies = &mut pallet.dependencies;
+    let node_rpc_dependencies = pallet
+        .node_rpc
+        .iter_mut()
+        .flat_map(|node_rpc| node_rpc.dependencies.iter_mut().flatten());
+    std::iter::once(&mut dependencies.pallet)
+        .chain(dependencies.additional_pallets.iter_mut().flatten())
+        .chain(node_rpc_dependencies)
+        .filter(|dependency| dependency.tag.is_some())
+        .for_each(|dependency| match dependency.git_repo.as_deref() {
+            Some(POLKADOT_SDK_REPO | FRONTIER_REPO) if template_type.follows_stable_branch() => {
+                dependency.tag = None;
+                dependency.branch = Some(sdk_version.stable_branch().to_string());
+            }
+            Some(POLKADOT_SDK_REPO) => dependency.tag = Some(sdk_version.to_string()),
+            _ => {}
+        });
+}
+
+fn is_upper_camel_case(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase()) && chars.all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Reports a pallet that is not marked as supporting the template, suggesting a variant of
+/// the same pallet crate from the catalog that is.
+fn unsupported_pallet(
+    catalog: &HashMap<String, PalletConfig>,
+    pallet: &PalletConfig,
+    template_type: &TemplateType,
+    required_by: Option<String>,
+) -> Option<UnsupportedPallet> {
+    if pallet.metadata.supported_template.contains(template_type) {
+        return None;
+    }
+    let mut variants = catalog
+        .values()
+        .filter(|variant| {
+            variant.name != pallet.name
+                && !variant.metadata.is_instance.unwrap_or_default()
+                && variant.dependencies.pallet.package == pallet.dependencies.pallet.package
+                && variant.metadata.supported_template.contains(template_type)
+        })
+        .map(|variant| variant.name.clone())
+        .collect::<Vec<_>>();
+    variants.sort();
+    Some(UnsupportedPallet {
+        pallet: pallet.name.clone(),
+        template: template_type.clone(),
+        required_by,
+        suggestion: variants.into_iter().next(),
+    })
+}
+
+/// Describes every pair of pallets in the set that declares a conflict, in either direction.
+pub fn pallet_conflicts<'a>(pallets: impl IntoIterator<Item = &'a PalletConfig>) -> Vec<String> {
+    let pallets = pallets.into_iter().collect::<Vec<_>>();
+    let mut conflicts = BTreeSet::new();
+    for pallet in &pallets {
+        for other in pallet.dependencies.conflicts_with.iter().flatten() {
+            if pallets.iter().any(|selected| &selected.name == other) {
+                let mut pair = [pallet.name.as_str(), other.as_str()];
+                pair.sort();
+                conflicts.insert(format!(
+                    "{} cannot be used together with {}",
+                    pair[0], pair[1]
+                ));
+            }
+        }
+    }
+    conflicts.into_iter().collect()
+}
+
+/// Checks a user supplied pallet before it is rendered next to the catalog pallets.
+fn validate_custom_pallet(
+    pallet: &PalletConfig,
+    catalog: &HashMap<String, PalletConfig>,
+) -> Result<()> {
+    let invalid = |reason: &str| {
+        Err(CodeGeneratorServiceError::CustomPalletError(format!(
+            "{}: {}",
+            pallet.name, reason
+        )))
+    };
+    if catalog.contains_key(&pallet.name) {
+        return invalid("the name is already used by a catalog pallet");
+    }
+    if !pallet
+        .dependencies
+        .pallet
+        .git_repo
+        .as_ref()
+        .is_some_and(|git_repo| git_repo.starts_with("https://"))
+    {
+        return invalid("`dependencies.pallet.git_repo` must be an https git URL");
+    }
+    validate_dependencies(pallet)
+        .and_then(|_| validate_parameter_types(pallet))
+        .and_then(|_| validate_metadata(pallet))
+        .map_err(|e| CodeGeneratorServiceError::CustomPalletError(e.to_string()))?;
+    match pallet.runtime.construct_runtime.runtime.as_slice() {
+        [runtime_name, _] if is_upper_camel_case(runtime_name) => {}
+        _ => {
+            return invalid(
+                "`construct_runtime.runtime` must be [\"Name\", \"path::Pallet<Runtime>\"]",
+            )
+        }
+    }
+    for required_pallet in pallet.dependencies.required_pallets.iter().flatten() {
+        if !catalog.contains_key(required_pallet) {
+            return invalid(&format!(
+                "required pallet {} is not in the catalog",
+                required_pallet
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn is_snake_case(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Checks that a pallet scaffold renders to a crate with valid, unique identifiers.
+fn validate_pallet_scaffold(
+    scaffold: &PalletScaffold,
+    catalog: &HashMap<String, PalletConfig>,
+) -> Result<()> {
+    let invalid = |reason: String| {
+        Err(CodeGeneratorServiceError::PalletScaffoldError(format!(
+            "{}: {}",
+            scaffold.name, reason
+        )))
+    };
+    if !is_snake_case(&scaffold.name) {
+        return invalid("the name must be a snake_case identifier".to_string());
+    }
+    // `pallets/template` ships with the templates
+    if scaffold.name == "template" {
+        return invalid("the name is already used by the template pallet".to_string());
+    }
+    if catalog.contains_key(&scaffold_pallet_name(scaffold)) {
+        return invalid("the name is already used by a catalog pallet".to_string());
+    }
+    // the description ends up in a `//!` line of the crate docs
+    if scaffold
+        .description
+        .as_deref()
+        .is_some_and(|description| description.contains(char::is_control))
+    {
+        return invalid("the description must be a single line".to_string());
+    }
+    let mut seen = HashSet::new();
+    let storage = scaffold.storage.iter().flatten();
+    let events = scaffold.events.iter().flatten();
+    let constants = scaffold.constants.iter().flatten();
+    let type_names = storage
+        .map(|item| ("storage", &item.name))
+        .chain(events.clone().map(|event| ("event", &event.name)))
+        .chain(
+            scaffold
+                .errors
+                .iter()
+                .flatten()
+                .map(|error| ("error", error)),
+        )
+        .chain(
+            constants
+                .clone()
+                .map(|constant| ("constant", &constant.name)),
+        );
+    for (kind, name) in type_names {
+        if !is_upper_camel_case(name) {
+            return invalid(format!("{} {} is not UpperCamelCase", kind, name));
+        }
+        if !seen.insert((kind, name)) {
+            return invalid(format!("{} {} is declared more than once", kind, name));
+        }
+    }
+    let fields = scaffold
+        .calls
+        .iter()
+        .flatten()
+        .flat_map(|call| call.args.iter().flatten())
+        .chain(events.flat_map(|event| event.fields.iter().flatten()));
+    for call in scaffold.calls.iter().flatten() {
+        if !is_snake_case(&call.name) {
+            return invalid(format!("call {} is not snake_case", call.name));
+        }
+        if !seen.insert(("call", &call.name)) {
+            return invalid(format!("call {} is declared more than once", call.name));
+        }
+    }
+    for field in fields {
+        if !is_snake_case(&field.name) || field.field_type.trim().is_empty() {
+            return invalid(format!(
+                "field {} needs a snake_case name and a type",
+                field.name
+            ));
+        }
+    }
+    for constant in constants {
+        if constant.const_type.trim().is_empty() || constant.value.trim().is_empty() {
+            return invalid(format!(
+                "constant {} needs a type and a value",
+                constant.name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Name under which a scaffolded pallet appears next to the catalog pallets.
+fn scaffold_pallet_name(scaffold: &PalletScaffold) -> String {
+    format!("Pallet {}", scaffold_runtime_name(&scaffold.name))
+}
+
+/// Describes a scaffolded pallet like a catalog pallet so the runtime, manifest and
+/// benchmarks are rendered the usual way. Constants become parameter types of the
+/// runtime, integer ones are configurable through `value`.
+fn scaffold_pallet_config(
+    scaffold: &PalletScaffold,
+    template_type: &TemplateType,
+    sdk_version: &SdkVersion,
+) -> PalletConfig {
+    let runtime_name = scaffold_runtime_name(&scaffold.name);
+    let module_name = format!("pallet_{}", scaffold.name);
+    let description = scaffold
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("FRAME pallet {}", scaffold.name));
+    let mut pallet_traits = HashMap::new();
+    if scaffold
+        .events
+        .as_ref()
+        .is_some_and(|events| !events.is_empty())
+    {
+        pallet_traits.insert("RuntimeEvent".to_string(), "RuntimeEvent".to_string());
+    }
+    let mut optional_parameter_types = HashMap::new();
+    for constant in scaffold.constants.iter().flatten() {
+        let parameter_name = format!("{}{}", runtime_name, constant.name);
+        pallet_traits.insert(constant.name.clone(), parameter_name.clone());
+        let is_integer = constant.value.parse::<i128>().is_ok();
+        optional_parameter_types.insert(
+            constant.name.clone(),
+            ParameterType {
+                name: parameter_name,
+                description: format!("`{}` of {}", constant.name, module_name),
+                prefix: ParameterTypePrefix::Const,
+                p_type: constant.const_type.clone(),
+                // integer constants stay configurable, anything else is copied verbatim
+                expression: (!is_integer).then(|| ParameterTypeExpression {
+                    default_unit: constant.value.clone(),
+                    default_multiplier: None,
+                    format: "{}{}".to_string(),
+                    possible_units: vec![],
+                    multiplier_configurable: false,
+                    configured_multiplier: None,
+                    configured_unit: None,
+                }),
+                value: is_integer.then(|| ParameterValue {
+                    kind: ParameterValueKind::Integer,
+                    min: None,
+                    max: None,
+                    variants: None,
+                    unit: None,
+                    default_value: constant.value.clone(),
+                    configured_value: None,
+                }),
+            },
+        );
+    }
+    // the runtime only renders `impl Config` blocks for pallets with associated types
+    let additional_pallet_impl_code = pallet_traits
+        .is_empty()
+        .then(|| format!("impl {}::Config for Runtime {{}}", module_name));
+    PalletConfig {
+        name: scaffold_pallet_name(scaffold),
+        metadata: PalletMetadata {
+            short_description: description.clone(),
+            description,
+            sdk_versions: vec![*sdk_version],
+            license: Some("Unlicense".to_string()),
+            authors: vec![],
+            category: None,
+            size: 0,
+            updated: None,
+            is_essential: None,
+            supported_template: vec![template_type.clone()],
+            is_instance: None,
+            is_instantiable: None,
+            use_cases: None,
+        },
+        runtime: PalletRuntimeConfig {
+            construct_runtime: PalletConstructRuntimeConfig {
+                runtime: vec![runtime_name, format!("{}::Pallet<Runtime>", module_name)],
+                pallet_index: None,
+                instance_name: None,
+            },
+            pallet_traits,
+            pallet_trait_alternatives: None,
+            when: None,
+            additional_pallet_impl_code,
+            genesis_config: None,
+            additional_chain_spec_code: None,
+            additional_runtime_lib_code: None,
+            runtime_api_imports: None,
+            runtime_api_code: None,
+            optional_parameter_types: Some(optional_parameter_types),
+        },
+        dependencies: PalletDependencyConfig {
+            pallet: CargoComplexDependency {
+                package: format!("pallet-{}", scaffold.name.replace('_', "-")),
+                version: None,
+                alias: format!("pallet {}", scaffold.name.replace('_', " ")),
+                default_features: false,
+                git_repo: None,
+                tag: None,
+                branch: None,
+                rev: None,
+                path: Some(format!("../pallets/{}", scaffold.name)),
+                features: None,
+                runtime_features: None,
+            },
+            additional_pallets: None,
+            additional_deps: None,
+            required_pallets: None,
+            conflicts_with: None,
+        },
+        node_rpc: None,
+        benchmarks: Some(PalletBenchmarksConfig {
+            module: module_name,
+            runtime_type: None,
+        }),
+    }
+}
+
+/// Replaces associated types of a pallet with the selected alternatives once
+/// every pallet an alternative refers to is part of the runtime.
+fn select_pallet_traits(
+    pallet_configs: &mut HashMap<String, PalletConfig>,
+    pallet_name: &str,
+    selections: &HashMap<String, String>,
+) -> Result<()> {
+    let invalid = |reason: String| {
+        Err(CodeGeneratorServiceError::PalletTraitError(format!(
+            "{}: {}",
+            pallet_name, reason
+        )))
+    };
+    let pallet = pallet_configs
+        .get(pallet_name)
+        .ok_or_else(|| CodeGeneratorServiceError::PalletNotFoundError(pallet_name.to_string()))?;
+    let mut selected = vec![];
+    for (associated_type, alternative_name) in selections {
+        let Some(alternatives) = pallet
+            .runtime
+            .pallet_trait_alternatives
+            .as_ref()
+            .and_then(|alternatives| alternatives.get(associated_type))
+        else {
+            return invalid(format!("{} has no alternatives", associated_type));
+        };
+        let Some(alternative) = alternatives
+            .iter()
+            .find(|alternative| &alternative.name == alternative_name)
+        else {
+            return invalid(format!(
+                "{} is not one of the {} alternatives",
+                alternative_name, associated_type
+            ));
+        };
+        let missing_pallets = alternative
+            .required_pallets
+            .iter()
+            .flatten()
+            .filter(|required_pallet| !pallet_configs.contains_key(*required_pallet))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !missing_pallets.is_empty() {
+            return invalid(format!(
+                "{} of {} needs {}",
+                alternative_name,
+                associated_type,
+                missing_pallets.join(", ")
+            ));
+        }
+        selected.push((associated_type.clone(), alternative.value.clone()));
+    }
+    if let Some(pallet) = pallet_configs.get_mut(pallet_name) {
+        // an explicit selection wins over conditional wiring
+        for condition in pallet
+            .runtime
+            .when
+            .iter_mut()
+            .flat_map(|when| when.values_mut())
+        {
+            if let Some(pallet_traits) = condition.pallet_traits.as_mut() {
+                pallet_traits.retain(|associated_type, _| {
+                    !selected
+                        .iter()
+                        .any(|(selected_type, _)| selected_type == associated_type)
+                });
+            }
+        }
+        pallet.runtime.pallet_traits.extend(selected);
+    }
+    Ok(())
+}
+
+/// Lists why a configuration does not fit the declared parameter, empty when it does.
+fn parameter_configuration_errors(
+    parameter: &ParameterType,
+    input: &ParameterConfiguration,
+) -> Vec<String> {
+    let mut reasons = vec![];
+    if let Some(value_type) = &parameter.value {
+        if input.multiplier.is_some() || input.unit.is_some() {
+            reasons.push("typed parameters are configured with `value`".to_string());
+        }
+        if let Some(value) = &input.value {
+            if let Err(reason) = value_type.validate(&parameter.p_type, value) {
+                reasons.push(reason);
+            }
+        }
+        return reasons;
+    }
+    if input.value.is_some() {
+        reasons.push("the parameter is configured with `multiplier` and `unit`".to_string());
+    }
+    let Some(expression) = &parameter.expression else {
+        return reasons;
+    };
+    if input.multiplier.is_some() && !expression.multiplier_configurable {
+        reasons.push("the multiplier is not configurable".to_string());
+    }
+    if let Some(unit) = &input.unit {
+        if !expression.possible_units.contains(unit) {
+            reasons.push(format!(
+                "unit {} is not one of [{}]",
+                unit,
+                expression.possible_units.join(", ")
+            ));
+        }
+    }
+    reasons
+}
+
+/// Builds a named instance of an instantiable pallet. Parameter types get the
+/// instance name as prefix so every instance has its own `parameter_types!`.
+fn instantiate_pallet(base: &PalletConfig, instance_name: &str) -> Result<PalletConfig> {
+    if !is_upper_camel_case(instance_name) {
+        return Err(CodeGeneratorServiceError::PalletInstanceError(format!(
+            "{} is not an UpperCamelCase identifier",
+            instance_name
+        )));
+    }
+    let mut pallet = base.clone();
+    pallet.name = instance_name.to_string();
+    pallet.metadata.is_instance = Some(true);
+    pallet.runtime.construct_runtime.runtime[0] = instance_name.to_string();
+    pallet.runtime.construct_runtime.instance_name = Some(instance_name.to_string());
+    if let Some(parameter_types) = pallet.runtime.optional_parameter_types.as_mut() {
+        for parameter_type in parameter_types.values_mut() {
+            let instance_parameter = format!("{}{}", instance_name, parameter_type.name);
+            let conditional_traits = pallet
+                .runtime
+                .when
+                .iter_mut()
+                .flat_map(|when| when.values_mut())
+                .flat_map(|condition| condition.pallet_traits.iter_mut());
+            for pallet_traits in
+                std::iter::once(&mut pallet.runtime.pallet_traits).chain(conditional_traits)
+            {
+                for value in pallet_traits.values_mut() {
+                    if *value == parameter_type.name {
+                        *value = instance_parameter.clone();
+                    }
+                }
+            }
+            parameter_type.name = instance_parameter;
+        }
+    }
+    if let Some(genesis_config) = pallet.runtime.genesis_config.as_mut() {
+        // genesis JSON keys are the lowerCamelCase runtime names
+        genesis_config.config_struct_name = instance_name[..1].to_lowercase() + &instance_name[1..];
+    }
+    // runtime APIs and RPCs are provided by the default instance only
+    pallet.runtime.runtime_api_imports = None;
+    pallet.runtime.runtime_api_code = None;
+    pallet.node_rpc = None;
+    Ok(pallet)
+}
+
+/// Records the runtime alias of a pallet, returning whoever already declares it.
+fn claim_runtime_alias(
+    runtime_aliases: &mut HashMap<String, String>,
+    pallet: &PalletConfig,
+) -> Option<String> {
+    match runtime_aliases.entry(runtime_alias(pallet)) {
+        Entry::Occupied(owner) => Some(owner.get().clone()),
+        Entry::Vacant(entry) => {
+            entry.insert(pallet.name.clone());
+            None
+        }
+    }
+}
+
+/// Loads the pallet configs and templates and checks them against each other, so a catalog
+/// that could only fail during a generation is rejected up front.
+pub async fn load_catalog(config_directory: &str, templates_directory: &str) -> Result<Catalog> {
+    let pallet_configs = load_configs(Path::new(config_directory).to_path_buf()).await?;
+    let templates = load_templates(Path::new(templates_directory).to_path_buf()).await?;
+    let template_sdk_versions =
+        load_template_sdk_versions(Path::new(templates_directory).to_path_buf()).await?;
+    let template_files = load_template_files(Path::new(templates_directory).to_path_buf()).await?;
+    let pallet_scaffold_files =
+        load_template_files(Path::new(PALLET_SCAFFOLD_DIRECTORY).to_path_buf()).await?;
+    let template_runtime_aliases = load_template_runtime_aliases(&template_files, &templates)?;
+
+    let mut problems = config_reference_errors(&pallet_configs)
+        .into_iter()
+        .map(|(pallet, problem)| format!("{}: {}", pallet, problem))
+        .collect::<Vec<_>>();
+    for template in &templates {
+        if template_sdk_versions
+            .get(template)
+            .is_none_or(Vec::is_empty)
+        {
+            problems.push(format!(
+                "{} has no supported polkadot-sdk release",
+                template
+            ));
+        }
+    }
+    if !problems.is_empty() {
+        return Err(CodeGeneratorServiceError::InvalidCatalogError(problems));
+    }
+    Ok(Catalog {
+        pallet_configs,
+        templates,
+        template_sdk_versions,
+        template_runtime_aliases,
+        template_files,
+        pallet_scaffold_files,
+    })
+}
+
 pub struct CodeGeneratorService<ZB: 'static> {
-    #[allow(dead_code)]
     config_directory: String,
     templates_directory: String,
-    pallet_configs: HashMap<String, PalletConfig>,
-    templates: Vec<TemplateType>,
+    catalog: ArcSwap<Catalog>,
     archiver_service: Arc<dyn ArchiverService<ZippedBuffer = ZB>>,
 }
 
@@ -29,146 +658,535 @@ impl<ZB: 'static + Send> CodeGeneratorService<ZB> {
     ) -> Result<Self> {
         let config_directory = std::env::var("CONFIG_DIRECTORY")?;
         let templates_directory = std::env::var("TEMPLATES_DIRECTORY")?;
-        let pallet_configs = load_configs(Path::new(&config_directory).to_path_buf()).await?;
-        let templates = load_templates(Path::new(&templates_directory).to_path_buf()).await?;
+        let catalog = load_catalog(&config_directory, &templates_directory).await?;
 
         Ok(Self {
             config_directory,
             templates_directory,
-            pallet_configs,
-            templates,
+            catalog: ArcSwap::from_pointee(catalog),
             archiver_service,
         })
     }
-    fn filter_configs(
+    pub async fn add_pallets_to_archive(
         &self,
-        filter: Vec<String>,
+        zipper_buffer: ZB,
+        catalog: &Catalog,
+        pallet_configs: Vec<PalletConfig>,
         template_type: &TemplateType,
-    ) -> Result<HashMap<String, PalletConfig>> {
-        // Check if the pallets are supported
-        for pallet_name in filter.iter() {
-            if !self.pallet_configs.contains_key(pallet_name) {
-                return Err(CodeGeneratorServiceError::PalletNotFoundError(
-                    pallet_name.clone(),
-                ));
-            }
+        sdk_version: &SdkVersion,
+    ) -> Result<ZB>
+    where
+        ZB: 'static + Send,
+    {
+        let files = render_pallet_files(catalog, pallet_configs, template_type, sdk_version)?;
+        self.add_files_to_archive(zipper_buffer, files).await
+    }
+    async fn add_files_to_archive(
+        &self,
+        mut zipper_buffer: ZB,
+        files: Vec<(PathBuf, Vec<u8>)>,
+    ) -> Result<ZB> {
+        for (path, content) in files {
+            zipper_buffer = self
+                .archiver_service
+                .add_content_to_archive(zipper_buffer, &content, &path)
+                .await?;
         }
-
-        // Get the required pallets for the pallets in the list
-        let filtered: Vec<String> =
-            self.pallet_configs
-                .iter()
-                // Get the pallets that are in the list of pallet names
-                .filter(|(name, pallet)| {
-                    filter.contains(name)
-                        || pallet.metadata.is_essential.as_ref().is_some_and(
-                            |essential_templates| essential_templates.contains(template_type),
-                        )
-                })
-                // Get the required pallets for each pallet
-                .flat_map(|(pallet_name, pallet)| {
-                    let mut pallet_with_reqs = vec![pallet_name.clone()];
-                    if let Some(required_pallets) = pallet.dependencies.required_pallets.clone() {
-                        pallet_with_reqs.extend(required_pallets);
-                    }
-                    pallet_with_reqs
-                })
-                .collect::<Vec<String>>();
-
-        // create local coppy of the pallets
-        Ok(self
-            .pallet_configs
-            .iter()
-            .filter(|(name, _)| filtered.contains(*name))
-            .map(|(name, config)| (name.clone(), config.clone()))
-            .collect::<HashMap<_, _>>())
+        Ok(zipper_buffer)
     }
-    pub async fn add_pallets_to_archive(
+    /// Adds the crates of scaffolded pallets and the manifests of the workspace and its template pallet.
+    pub async fn add_pallet_scaffolds_to_archive(
         &self,
         zipper_buffer: ZB,
-        pallet_configs: Vec<PalletConfig>,
+        catalog: &Catalog,
+        pallet_scaffolds: &[PalletScaffold],
         template_type: &TemplateType,
+        sdk_version: &SdkVersion,
     ) -> Result<ZB>
     where
         ZB: 'static + Send,
     {
-        let manifest_file_path = format!("templates/{}/runtime/Cargo.toml.hbs", template_type);
-        let manifest_file_content =
-            generate_manifest_file_to_bytes(&manifest_file_path, &pallet_configs).unwrap();
-        let zipper_buffer = self
-            .archiver_service
-            .add_content_to_archive(
-                zipper_buffer,
-                &manifest_file_content,
-                Path::new("runtime/Cargo.toml"),
-            )
-            .await?;
+        let files =
+            render_pallet_scaffold_files(catalog, pallet_scaffolds, template_type, sdk_version)?;
+        self.add_files_to_archive(zipper_buffer, files).await
+    }
+}
 
-        let runtime_lib_file_path = format!("templates/{}/runtime/src/lib.rs.hbs", template_type);
-        let runtime_lib_file_content =
-            generate_runtime_lib_file_bytes(&runtime_lib_file_path, &pallet_configs).unwrap();
-        let zipper_buffer = self
-            .archiver_service
-            .add_content_to_archive(
-                zipper_buffer,
-                &runtime_lib_file_content,
-                Path::new("runtime/src/lib.rs"),
+/// Renders the runtime and node files that depend on the selected pallets from the `.hbs`
+/// files of the catalog.
+pub fn render_pallet_files(
+    catalog: &Catalog,
+    mut pallet_configs: Vec<PalletConfig>,
+    template_type: &TemplateType,
+    sdk_version: &SdkVersion,
+) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let sdk_sources = SdkSources::new(template_type, sdk_version);
+    let template_file = |path| required_template_file(catalog, template_type, path);
+    let runtime_lib_template = template_file("runtime/src/lib.rs.hbs")?;
+    assign_pallet_indices(
+        &mut pallet_configs,
+        &template_pallet_indices(runtime_lib_template),
+    )?;
+    // SoloFrontierChain has no rpc.rs.hbs, its `create_full` is generic over the block type that
+    // the `node_rpc` snippets of the catalog name, so pallets needing RPC wiring are rejected
+    let node_rpc_template = catalog.template_file(template_type, "node/src/rpc.rs.hbs");
+    if node_rpc_template.is_none() {
+        let unsupported = pallet_configs
+            .iter()
+            .filter(|pallet| pallet.node_rpc.is_some())
+            .map(|pallet| UnsupportedPallet {
+                pallet: pallet.name.clone(),
+                template: template_type.clone(),
+                required_by: None,
+                suggestion: None,
+            })
+            .collect::<Vec<_>>();
+        if !unsupported.is_empty() {
+            return Err(CodeGeneratorServiceError::UnsupportedPalletError(
+                unsupported,
+            ));
+        }
+    }
+    let manifest_template = template_file("runtime/Cargo.toml.hbs")?;
+    let node_manifest_template = template_file("node/Cargo.toml.hbs")?;
+    let conflicts =
+        dependency_conflicts(&pallet_configs, manifest_template, node_manifest_template);
+    if !conflicts.is_empty() {
+        return Err(CodeGeneratorServiceError::DependencyConflictError(
+            conflicts.join("; "),
+        ));
+    }
+
+    let mut files = vec![];
+    let manifest_file_content =
+        generate_manifest_file_to_bytes(manifest_template, &pallet_configs, &sdk_sources)
+            .map_err(|e| CodeGeneratorServiceError::OtherError(e.to_string()))?;
+    files.push((PathBuf::from("runtime/Cargo.toml"), manifest_file_content));
+
+    let runtime_lib_file_content =
+        generate_runtime_lib_file_bytes(runtime_lib_template, &pallet_configs)
+            .map_err(|e| CodeGeneratorServiceError::OtherError(e.to_string()))?;
+    files.push((
+        PathBuf::from("runtime/src/lib.rs"),
+        runtime_lib_file_content,
+    ));
+
+    // Templates that split runtime APIs and benchmarks out of lib.rs render them from the same data
+    for runtime_file in ["apis.rs", "benchmarks.rs"] {
+        let runtime_file_path = format!("runtime/src/{}.{}", runtime_file, HBS_SUFFIX);
+        if let Some(runtime_file_template) =
+            catalog.template_file(template_type, &runtime_file_path)
+        {
+            let runtime_file_content =
+                generate_runtime_lib_file_bytes(runtime_file_template, &pallet_configs)
+                    .map_err(|e| CodeGeneratorServiceError::OtherError(e.to_string()))?;
+            files.push((
+                PathBuf::from(format!("runtime/src/{}", runtime_file)),
+                runtime_file_content,
+            ));
+        }
+    }
+
+    let chain_spec_file_content = generate_chain_spec_file_bytes(
+        template_file("node/src/chain_spec.rs.hbs")?,
+        &pallet_configs,
+    )
+    .map_err(|e| CodeGeneratorServiceError::OtherError(e.to_string()))?;
+    files.push((
+        PathBuf::from("node/src/chain_spec.rs"),
+        chain_spec_file_content,
+    ));
+
+    let node_manifest_file_content =
+        generate_node_manifest_file_to_bytes(node_manifest_template, &pallet_configs, &sdk_sources)
+            .map_err(|e| CodeGeneratorServiceError::OtherError(e.to_string()))?;
+    files.push((PathBuf::from("node/Cargo.toml"), node_manifest_file_content));
+
+    if let Some(node_rpc_template) = node_rpc_template {
+        let node_rpc_file_content =
+            generate_node_rpc_file_bytes(node_rpc_template, &pallet_configs)
+                .map_err(|e| CodeGeneratorServiceError::OtherError(e.to_string()))?;
+        files.push((PathBuf::from("node/src/rpc.rs"), node_rpc_file_content));
+    }
+
+    checked_files(files, &pallet_configs)
+}
+
+/// Renders the crates of scaffolded pallets, the workspace manifest listing them and the
+/// manifest of the template pallet from the `.hbs` files of the catalog.
+pub fn render_pallet_scaffold_files(
+    catalog: &Catalog,
+    pallet_scaffolds: &[PalletScaffold],
+    template_type: &TemplateType,
+    sdk_version: &SdkVersion,
+) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let sdk_sources = SdkSources::new(template_type, sdk_version);
+    let mut files = vec![];
+    for scaffold in pallet_scaffolds {
+        files.extend(
+            generate_pallet_scaffold_files(
+                scaffold,
+                &catalog.pallet_scaffold_files,
+                template_type,
+                sdk_version,
             )
-            .await?;
+            .map_err(|e| CodeGeneratorServiceError::OtherError(e.to_string()))?,
+        );
+    }
 
-        Ok(zipper_buffer)
+    let workspace_manifest_template =
+        required_template_file(catalog, template_type, "Cargo.toml.hbs")?;
+    let workspace_manifest_file_content = generate_workspace_manifest_file_bytes(
+        workspace_manifest_template,
+        pallet_scaffolds,
+        &sdk_sources,
+    )
+    .map_err(|e| CodeGeneratorServiceError::OtherError(e.to_string()))?;
+    files.push((PathBuf::from("Cargo.toml"), workspace_manifest_file_content));
+
+    // not every template ships a template pallet
+    let template_pallet_manifest_path = format!("pallets/template/Cargo.toml.{}", HBS_SUFFIX);
+    if let Some(template_pallet_manifest_template) =
+        catalog.template_file(template_type, &template_pallet_manifest_path)
+    {
+        let template_pallet_manifest_file_content =
+            render_handlebars_template_to_bytes(template_pallet_manifest_template, &sdk_sources)
+                .map_err(|e| CodeGeneratorServiceError::OtherError(e.to_string()))?;
+        files.push((
+            PathBuf::from("pallets/template/Cargo.toml"),
+            template_pallet_manifest_file_content,
+        ));
+    }
+
+    checked_files(files, &[])
+}
+
+/// The snapshot of a `.hbs` file every template has to ship.
+fn required_template_file<'a>(
+    catalog: &'a Catalog,
+    template_type: &TemplateType,
+    path: &str,
+) -> Result<&'a str> {
+    catalog.template_file(template_type, path).ok_or_else(|| {
+        CodeGeneratorServiceError::OtherError(
+            TemplateRenderError::MissingTemplateFile(format!("{}/{}", template_type, path))
+                .to_string(),
+        )
+    })
+}
+
+/// Checks and formats rendered files, all of them have to parse.
+fn checked_files(
+    mut files: Vec<(PathBuf, Vec<u8>)>,
+    pallet_configs: &[PalletConfig],
+) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let invalid_files = check_generated_files(&mut files, pallet_configs);
+    if !invalid_files.is_empty() {
+        return Err(CodeGeneratorServiceError::InvalidGeneratedFileError(
+            invalid_files,
+        ));
+    }
+    Ok(files)
+}
+
+impl Catalog {
+    /// The snapshot of a `.hbs` file of a template, `path` is relative to its directory.
+    pub fn template_file(&self, template_type: &TemplateType, path: &str) -> Option<&str> {
+        self.template_files
+            .get(&Path::new(&template_type.to_string()).join(path))
+            .map(String::as_str)
     }
 
+    /// Validates a project against the catalog and returns its configured pallets.
+    fn project_pallets(
+        &self,
+        parameter_configs: &HashMap<String, Option<HashMap<String, ParameterConfiguration>>>,
+        template_type: &TemplateType,
+        options: &ProjectOptions,
+    ) -> Result<Vec<PalletConfig>> {
+        let pallets = self.apply_configs(parameter_configs, template_type, options)?;
+        if !self.templates.contains(template_type) {
+            return Err(CodeGeneratorServiceError::InvalidTemplateType(format!(
+                "{:?}",
+                template_type
+            )));
+        }
+        if !self
+            .template_sdk_versions
+            .get(template_type)
+            .is_some_and(|sdk_versions| sdk_versions.contains(&options.sdk_version))
+        {
+            return Err(CodeGeneratorServiceError::UnsupportedSdkVersion(format!(
+                "{} does not support {}",
+                template_type, options.sdk_version
+            )));
+        }
+        Ok(pallets)
+    }
+    fn filter_configs(
+        &self,
+        filter: Vec<String>,
+        template_type: &TemplateType,
+    ) -> Result<HashMap<String, PalletConfig>> {
+        let included = resolve_pallets(&self.pallet_configs, &filter, template_type).map_err(
+            |DependencyResolutionError::PalletNotFound(name)| {
+                CodeGeneratorServiceError::PalletNotFoundError(name)
+            },
+        )?;
+        let filtered_configs = included
+            .iter()
+            .map(|inclusion| {
+                let config = self.pallet_configs[&inclusion.name].clone();
+                (inclusion.name.clone(), config)
+            })
+            .collect::<HashMap<_, _>>();
+        let conflicts = pallet_conflicts(filtered_configs.values());
+        if !conflicts.is_empty() {
+            return Err(CodeGeneratorServiceError::PalletConflictError(conflicts));
+        }
+        let unsupported = included
+            .iter()
+            .filter_map(|inclusion| {
+                unsupported_pallet(
+                    &self.pallet_configs,
+                    &self.pallet_configs[&inclusion.name],
+                    template_type,
+                    inclusion.required_by.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        if !unsupported.is_empty() {
+            return Err(CodeGeneratorServiceError::UnsupportedPalletError(
+                unsupported,
+            ));
+        }
+        Ok(filtered_configs)
+    }
     fn apply_configs(
         &self,
         parameter_configs: &HashMap<String, Option<HashMap<String, ParameterConfiguration>>>,
         template_type: &TemplateType,
+        options: &ProjectOptions,
     ) -> Result<Vec<PalletConfig>> {
-        let mut filtered_configs =
-            self.filter_configs(parameter_configs.keys().cloned().collect(), template_type)?;
-        parameter_configs
+        let sdk_version = &options.sdk_version;
+        for custom_pallet in &options.custom_pallets {
+            validate_custom_pallet(custom_pallet, &self.pallet_configs)?;
+        }
+        let mut custom_pallets = options.custom_pallets.clone();
+        for scaffold in &options.pallet_scaffolds {
+            validate_pallet_scaffold(scaffold, &self.pallet_configs)?;
+            custom_pallets.push(scaffold_pallet_config(scaffold, template_type, sdk_version));
+        }
+        let is_custom = |name: &String| {
+            custom_pallets
+                .iter()
+                .any(|custom_pallet| &custom_pallet.name == name)
+        };
+        let mut requested = parameter_configs
+            .keys()
+            .filter(|name| !is_custom(name))
+            .cloned()
+            .collect::<Vec<_>>();
+        for custom_pallet in &custom_pallets {
+            requested.extend(
+                custom_pallet
+                    .dependencies
+                    .required_pallets
+                    .clone()
+                    .unwrap_or_default(),
+            );
+        }
+        let mut instance_configs = vec![];
+        for (pallet_name, instances) in &options.pallet_instances {
+            let base = self.pallet_configs.get(pallet_name).ok_or_else(|| {
+                CodeGeneratorServiceError::PalletNotFoundError(pallet_name.clone())
+            })?;
+            if !base.metadata.is_instantiable.unwrap_or_default() {
+                return Err(CodeGeneratorServiceError::PalletInstanceError(format!(
+                    "{} is not instantiable",
+                    pallet_name
+                )));
+            }
+            // instances need whatever their pallet requires
+            requested.extend(
+                base.dependencies
+                    .required_pallets
+                    .clone()
+                    .unwrap_or_default(),
+            );
+            for instance in instances {
+                instance_configs.push((
+                    instantiate_pallet(base, &instance.name)?,
+                    instance.parameters.clone(),
+                ));
+            }
+        }
+        let mut filtered_configs = self.filter_configs(requested, template_type)?;
+        // instances and custom pallets are not part of the resolution
+        let unsupported = instance_configs
             .iter()
-            .filter_map(|(name, config)| config.clone().map(|config| (name, config)))
-            .for_each(|(name, config)| {
-                let pallet_to_configure = filtered_configs.get_mut(name).unwrap();
-                for input in config {
-                    if let Some(parameter) = pallet_to_configure
-                        .runtime
-                        .optional_parameter_types
-                        .as_mut()
-                        .unwrap()
-                        .get_mut(&input.0)
-                    {
-                        parameter.expression.configured_multiplier = input.1.multiplier;
-                        parameter.expression.configured_unit = input.1.unit;
+            .map(|(pallet, _)| pallet)
+            .chain(custom_pallets.iter())
+            .filter_map(|pallet| {
+                unsupported_pallet(&self.pallet_configs, pallet, template_type, None)
+            })
+            .collect::<Vec<_>>();
+        if !unsupported.is_empty() {
+            return Err(CodeGeneratorServiceError::UnsupportedPalletError(
+                unsupported,
+            ));
+        }
+        // aliases of the catalog pallets are not checked, the catalog is trusted to pick
+        // distinct ones for the pallets a template supports
+        let mut runtime_aliases = RUNTIME_TYPES
+            .iter()
+            .map(|alias| (alias.to_string(), "the runtime".to_string()))
+            .chain(
+                self.template_runtime_aliases
+                    .get(template_type)
+                    .into_iter()
+                    .flatten()
+                    .map(|alias| (alias.clone(), format!("the {} template", template_type))),
+            )
+            .collect::<HashMap<_, _>>();
+        for pallet in filtered_configs.values() {
+            runtime_aliases
+                .entry(runtime_alias(pallet))
+                .or_insert_with(|| pallet.name.clone());
+        }
+        let mut instance_parameters = HashMap::new();
+        for (pallet, parameters) in instance_configs {
+            if filtered_configs.contains_key(&pallet.name) {
+                return Err(CodeGeneratorServiceError::PalletInstanceError(format!(
+                    "{} is used more than once",
+                    pallet.name
+                )));
+            }
+            if let Some(owner) = claim_runtime_alias(&mut runtime_aliases, &pallet) {
+                return Err(CodeGeneratorServiceError::PalletInstanceError(format!(
+                    "{} is already used as runtime name by {}",
+                    pallet.name, owner
+                )));
+            }
+            if let Some(parameters) = parameters {
+                instance_parameters.insert(pallet.name.clone(), Some(parameters));
+            }
+            filtered_configs.insert(pallet.name.clone(), pallet);
+        }
+        for (name, pallet) in filtered_configs.iter_mut() {
+            if !pallet.metadata.sdk_versions.contains(sdk_version) {
+                return Err(CodeGeneratorServiceError::UnsupportedSdkVersion(format!(
+                    "{} does not support {}",
+                    name, sdk_version
+                )));
+            }
+            pin_sdk_version(pallet, template_type, sdk_version);
+        }
+        // custom pallets pin their own sources, the catalog releases do not apply to them
+        for custom_pallet in custom_pallets {
+            if filtered_configs
+                .insert(custom_pallet.name.clone(), custom_pallet.clone())
+                .is_some()
+            {
+                return Err(CodeGeneratorServiceError::CustomPalletError(format!(
+                    "{} is used more than once",
+                    custom_pallet.name
+                )));
+            }
+            if let Some(owner) = claim_runtime_alias(&mut runtime_aliases, &custom_pallet) {
+                return Err(CodeGeneratorServiceError::CustomPalletError(format!(
+                    "runtime name {} of {} is already used by {}",
+                    runtime_alias(&custom_pallet),
+                    custom_pallet.name,
+                    owner
+                )));
+            }
+        }
+        for (name, selections) in &options.pallet_traits {
+            select_pallet_traits(&mut filtered_configs, name, selections)?;
+        }
+        for (name, index) in &options.pallet_indices {
+            let pallet_to_configure = filtered_configs
+                .get_mut(name)
+                .ok_or_else(|| CodeGeneratorServiceError::PalletNotFoundError(name.clone()))?;
+            pallet_to_configure.runtime.construct_runtime.pallet_index = Some(*index);
+        }
+        let mut invalid_parameters = vec![];
+        for (name, config) in parameter_configs
+            .iter()
+            .chain(instance_parameters.iter())
+            .filter_map(|(name, config)| config.as_ref().map(|config| (name, config)))
+        {
+            let pallet_to_configure = filtered_configs
+                .get_mut(name)
+                .ok_or_else(|| CodeGeneratorServiceError::PalletNotFoundError(name.clone()))?;
+            let mut parameter_names = config.keys().collect::<Vec<_>>();
+            parameter_names.sort();
+            for parameter_name in parameter_names {
+                let input = &config[parameter_name];
+                let parameter = pallet_to_configure
+                    .runtime
+                    .optional_parameter_types
+                    .as_mut()
+                    .and_then(|parameter_types| parameter_types.get_mut(parameter_name));
+                let reasons = match parameter.as_deref() {
+                    None => vec!["the pallet has no such parameter".to_string()],
+                    Some(parameter) => parameter_configuration_errors(parameter, input),
+                };
+                if !reasons.is_empty() {
+                    invalid_parameters.extend(reasons.into_iter().map(|reason| InvalidParameter {
+                        pallet: name.clone(),
+                        parameter: parameter_name.clone(),
+                        reason,
+                    }));
+                } else if let Some(parameter) = parameter {
+                    if let Some(value_type) = parameter.value.as_mut() {
+                        value_type.configured_value = input.value.clone();
+                    }
+                    if let Some(expression) = parameter.expression.as_mut() {
+                        expression.configured_multiplier = input.multiplier;
+                        expression.configured_unit = input.unit.clone();
                     }
                 }
-            });
+            }
+        }
+        if !invalid_parameters.is_empty() {
+            invalid_parameters.sort_by(|a, b| a.pallet.cmp(&b.pallet));
+            return Err(CodeGeneratorServiceError::InvalidParameterConfiguration(
+                invalid_parameters,
+            ));
+        }
         Ok(filtered_configs.values().cloned().collect::<Vec<_>>())
     }
 }
 #[async_trait]
 impl<ZB: 'static + Send> CodeGenerator for CodeGeneratorService<ZB> {
-    fn pallet_configs(&self) -> &HashMap<String, PalletConfig> {
-        &self.pallet_configs
+    fn catalog(&self) -> Arc<Catalog> {
+        self.catalog.load_full()
     }
 
-    fn templates(&self) -> &Vec<TemplateType> {
-        &self.templates
+    async fn reload_catalog(&self) -> Result<Arc<Catalog>> {
+        let catalog =
+            Arc::new(load_catalog(&self.config_directory, &self.templates_directory).await?);
+        self.catalog.store(catalog.clone());
+        info!(
+            "Reloaded catalog with {} pallets and {} templates",
+            catalog.pallet_configs.len(),
+            catalog.templates.len()
+        );
+        Ok(catalog)
     }
 
     async fn generate_project_archive(
         &self,
         pallets: &HashMap<String, Option<HashMap<String, ParameterConfiguration>>>,
         template_type: &TemplateType,
+        options: &ProjectOptions,
     ) -> Result<Vec<u8>> {
-        let pallets = self.apply_configs(pallets, template_type)?;
-        if !self.templates.contains(template_type) {
-            return Err(CodeGeneratorServiceError::InvalidTemplateType(format!(
-                "{:?}",
-                template_type
-            )));
-        }
+        // the snapshot taken here is used until the archive is complete
+        let catalog = self.catalog();
+        let sdk_version = &options.sdk_version;
+        let pallets = catalog.project_pallets(pallets, template_type, options)?;
 
         let template_path = Path::new(&self.templates_directory).join(template_type.to_string());
 
@@ -178,11 +1196,39 @@ impl<ZB: 'static + Send> CodeGenerator for CodeGeneratorService<ZB> {
             .await?;
 
         let zipped_buffer = self
-            .add_pallets_to_archive(zipped_buffer, pallets, template_type)
+            .add_pallets_to_archive(zipped_buffer, &catalog, pallets, template_type, sdk_version)
+            .await?;
+        let zipped_buffer = self
+            .add_pallet_scaffolds_to_archive(
+                zipped_buffer,
+                &catalog,
+                &options.pallet_scaffolds,
+                template_type,
+                sdk_version,
+            )
             .await?;
         let zipped_data = self.archiver_service.close_archive(zipped_buffer).await?;
         Ok(zipped_data)
     }
+    async fn preview_project(
+        &self,
+        pallets: &HashMap<String, Option<HashMap<String, ParameterConfiguration>>>,
+        template_type: &TemplateType,
+        options: &ProjectOptions,
+    ) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let catalog = self.catalog();
+        let pallets = catalog.project_pallets(pallets, template_type, options)?;
+        let mut files =
+            render_pallet_files(&catalog, pallets, template_type, &options.sdk_version)?;
+        files.extend(render_pallet_scaffold_files(
+            &catalog,
+            &options.pallet_scaffolds,
+            template_type,
+            &options.sdk_version,
+        )?);
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(files)
+    }
     async fn unpack_archive_to_folder(&self, buffer: Vec<u8>, output: &Path) -> Result<()> {
         self.archiver_service
             .unpack_archive_to_folder(buffer, output)
@@ -194,46 +1240,1041 @@ impl<ZB: 'static + Send> CodeGenerator for CodeGeneratorService<ZB> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::services::{
-        async_zip::AsyncZipArchiverService,
-        code_generator::{
-            service::CodeGeneratorService, templating::handle_templates::HBS_SUFFIX, CodeGenerator,
-        },
+    use crate::api::handlers::generate_project_handler::PalletInstance;
+    use crate::services::code_generator::{
+        load_configs::LoadConfigsError,
+        templating::handle_templates::HBS_SUFFIX,
+        test_utils::{catalog, code_generator, kitty_market_scaffold, template, unused_imports},
+        types::PalletNodeRpcConfig,
     };
+    use crate::services::{async_zip::AsyncZipArchiverService, traits::archiver::ArchiverService};
+
     #[tokio::test]
     async fn test_add_archived_pallets() {
-        dotenv::from_filename(".env.local").ok();
-        let archiver = Arc::new(AsyncZipArchiverService);
-        let cg = CodeGeneratorService::try_new(archiver.clone()).await;
-        assert!(cg.is_ok());
-        let cg = cg.unwrap();
-        let pallets = cg.pallet_configs();
-        let pallets = pallets.iter().map(|(_, v)| v.clone()).collect();
+        let cg = code_generator().await;
+        let archiver = AsyncZipArchiverService;
+        let catalog = cg.catalog();
+        let pallets = catalog.pallet_configs.values().cloned().collect();
         let zipper_buffer = archiver
             .archive_folder(Path::new("templates/SoloChain"), HBS_SUFFIX)
             .await;
-        let cg = CodeGeneratorService::try_new(archiver.clone()).await;
-        assert!(cg.is_ok());
-        let cg = cg.unwrap();
         let zipper_buffer = cg
-            .add_pallets_to_archive(zipper_buffer.unwrap(), pallets, &TemplateType::SoloChain)
+            .add_pallets_to_archive(
+                zipper_buffer.unwrap(),
+                &catalog,
+                pallets,
+                &TemplateType::SoloChain,
+                &SdkVersion::default(),
+            )
             .await;
         // save zipper_buffer to file test.zip in root directory
         let bytes = archiver.close_archive(zipper_buffer.unwrap()).await;
         assert!(bytes.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_minimal_template() {
+        let cg = code_generator().await;
+        assert!(cg.catalog().templates.contains(&TemplateType::Minimal));
+        let pallets = cg
+            .catalog()
+            .pallet_configs
+            .values()
+            .filter(|pallet| {
+                pallet
+                    .metadata
+                    .supported_template
+                    .contains(&TemplateType::Minimal)
+                    && !pallet.metadata.is_instance.unwrap_or_default()
+            })
+            .map(|pallet| (pallet.name.clone(), None))
+            .collect::<HashMap<_, _>>();
+        let archive = cg
+            .generate_project_archive(&pallets, &TemplateType::Minimal, &Default::default())
+            .await;
+        assert!(archive.is_ok());
+
+        // blocks are sealed by the node, timestamps must not drive Aura
+        let mut essential = cg
+            .catalog()
+            .apply_configs(&HashMap::new(), &TemplateType::Minimal, &Default::default())
+            .unwrap();
+        assign_pallet_indices(&mut essential, &[0]).unwrap();
+        let mut names = essential
+            .iter()
+            .map(|pallet| pallet.name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "Pallet Balances",
+                "Pallet Sudo",
+                "Pallet Timestamp",
+                "Pallet Transaction Payment"
+            ]
+        );
+        let runtime_lib = generate_runtime_lib_file_bytes(
+            &template("Minimal/runtime/src/lib.rs.hbs"),
+            &essential,
+        )
+        .unwrap();
+        let runtime_lib = String::from_utf8(runtime_lib).unwrap();
+        assert!(runtime_lib.contains("type OnTimestampSet = ();"));
+        assert!(!runtime_lib.contains("Aura"));
+    }
+
+    #[tokio::test]
+    async fn test_runtime_imports() {
+        let catalog = catalog().await;
+        for template_type in [TemplateType::SoloChain, TemplateType::Minimal] {
+            let files = render_pallet_files(
+                &catalog,
+                catalog
+                    .apply_configs(&HashMap::new(), &template_type, &Default::default())
+                    .unwrap(),
+                &template_type,
+                &SdkVersion::default(),
+            )
+            .unwrap();
+            let (_, runtime_lib) = files
+                .iter()
+                .find(|(path, _)| path == Path::new("runtime/src/lib.rs"))
+                .unwrap();
+            assert_eq!(
+                unused_imports(std::str::from_utf8(runtime_lib).unwrap()),
+                Vec::<String>::new(),
+                "{}",
+                template_type
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_filter_configs() {
-        dotenv::from_filename(".env.local").ok();
-        let archiver = Arc::new(AsyncZipArchiverService);
-        let cg = CodeGeneratorService::try_new(archiver.clone()).await;
-        let cg = cg.unwrap();
-        let filtered = cg.filter_configs(
+        let filtered = catalog().await.filter_configs(
             vec!["Pallet Bounties".to_string()],
             &TemplateType::SoloChain,
         );
         let filtered = filtered.unwrap();
         assert_eq!(filtered.len(), 9);
     }
+
+    #[tokio::test]
+    async fn test_assign_pallet_indices() {
+        let catalog = catalog().await;
+        let pallets = &catalog.pallet_configs;
+        let mut first = pallets.values().cloned().collect::<Vec<_>>();
+        let mut second = first.iter().rev().cloned().collect::<Vec<_>>();
+        assign_pallet_indices(&mut first, &[0, 1]).unwrap();
+        assign_pallet_indices(&mut second, &[0, 1]).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(
+            first[0].runtime.construct_runtime.pallet_index,
+            Some(FIRST_PALLET_INDEX)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_assign_pallet_indices_collisions() {
+        let catalog = catalog().await;
+        let mut pallets = vec![
+            catalog.pallet_configs["Pallet Bounties"].clone(),
+            catalog.pallet_configs["Pallet Treasury"].clone(),
+        ];
+        pallets[0].runtime.construct_runtime.pallet_index = Some(1);
+        let reserved = assign_pallet_indices(&mut pallets.clone(), &[0, 1]);
+        assert!(matches!(
+            reserved,
+            Err(CodeGeneratorServiceError::PalletIndexError(_))
+        ));
+        pallets[1].runtime.construct_runtime.pallet_index = Some(1);
+        let duplicate = assign_pallet_indices(&mut pallets, &[0]);
+        assert!(matches!(
+            duplicate,
+            Err(CodeGeneratorServiceError::PalletIndexError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sdk_versions() {
+        let catalog = catalog().await;
+        for template in &catalog.templates {
+            assert!(catalog.template_sdk_versions[template].contains(&SdkVersion::default()));
+        }
+        let pallets = catalog
+            .apply_configs(
+                &HashMap::from([("Pallet Nfts".to_string(), None)]),
+                &TemplateType::SoloChain,
+                &ProjectOptions::default(),
+            )
+            .unwrap();
+        for pallet in pallets {
+            assert_eq!(
+                pallet.dependencies.pallet.tag,
+                Some(SdkVersion::default().to_string())
+            );
+        }
+
+        // Frontier crates have no release tags, the Frontier templates follow the stable branch
+        let mut pallets = catalog
+            .apply_configs(
+                &HashMap::from([("Pallet Base Fee".to_string(), None)]),
+                &TemplateType::Frontier,
+                &ProjectOptions::default(),
+            )
+            .unwrap();
+        assign_pallet_indices(&mut pallets, &[]).unwrap();
+        let manifest = generate_manifest_file_to_bytes(
+            &template("Frontier/runtime/Cargo.toml.hbs"),
+            &pallets,
+            &SdkSources::new(&TemplateType::Frontier, &SdkVersion::default()),
+        )
+        .unwrap();
+        let manifest = String::from_utf8(manifest).unwrap();
+        assert!(manifest.contains(
+            "pallet-base-fee = { git = 'https://github.com/polkadot-evm/frontier', branch = 'stable2407', default-features = false }"
+        ));
+        assert!(!manifest.contains("polkadot-v1.14.0"));
+    }
+
+    #[tokio::test]
+    async fn test_sdk_version_pins() {
+        let catalog = catalog().await;
+        for sdk_version in [SdkVersion::PolkadotV1_14_0, SdkVersion::PolkadotStable2409] {
+            for template_type in &catalog.templates {
+                let mut pallets = vec![catalog.pallet_configs["Pallet Nfts"].clone()];
+                if template_type.follows_stable_branch() {
+                    pallets.push(catalog.pallet_configs["Pallet Ethereum"].clone());
+                }
+                for pallet in pallets.iter_mut() {
+                    pin_sdk_version(pallet, template_type, &sdk_version);
+                }
+                let mut files =
+                    render_pallet_files(&catalog, pallets, template_type, &sdk_version).unwrap();
+                files.extend(
+                    render_pallet_scaffold_files(
+                        &catalog,
+                        &[kitty_market_scaffold()],
+                        template_type,
+                        &sdk_version,
+                    )
+                    .unwrap(),
+                );
+                let (key, pin) = if template_type.follows_stable_branch() {
+                    ("branch", sdk_version.stable_branch().to_string())
+                } else {
+                    ("tag", sdk_version.to_string())
+                };
+                let frontier_branch = sdk_version.stable_branch().to_string();
+                let mut pinned = 0;
+                for (path, content) in files
+                    .iter()
+                    .filter(|(path, _)| path.ends_with("Cargo.toml"))
+                {
+                    let manifest: toml::Table =
+                        toml::from_str(std::str::from_utf8(content).unwrap()).unwrap();
+                    let workspace_dependencies = manifest
+                        .get("workspace")
+                        .and_then(|workspace| workspace.get("dependencies"));
+                    let dependencies = ["dependencies", "dev-dependencies", "build-dependencies"]
+                        .iter()
+                        .filter_map(|table| manifest.get(*table))
+                        .chain(workspace_dependencies)
+                        .filter_map(|table| table.as_table())
+                        .flat_map(|table| table.iter());
+                    for (name, dependency) in dependencies {
+                        let repo = dependency
+                            .get("git")
+                            .and_then(|git| git.as_str())
+                            .map(|git| git.trim_end_matches(".git"));
+                        let (key, pin) = match repo {
+                            Some("https://github.com/paritytech/polkadot-sdk") => (key, &pin),
+                            Some(FRONTIER_REPO) => ("branch", &frontier_branch),
+                            _ => continue,
+                        };
+                        assert_eq!(
+                            dependency.get(key).and_then(|pin| pin.as_str()),
+                            Some(pin.as_str()),
+                            "{} of {} in {} {}",
+                            name,
+                            path.display(),
+                            template_type,
+                            sdk_version
+                        );
+                        pinned += 1;
+                    }
+                }
+                assert!(pinned > 0);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pallet_instances() {
+        let catalog = catalog().await;
+        let instances = HashMap::from([(
+            "Pallet Collective".to_string(),
+            vec![
+                PalletInstance {
+                    name: "TechnicalCouncil".to_string(),
+                    parameters: Some(HashMap::from([(
+                        "CouncilMaxMembers".to_string(),
+                        ParameterConfiguration {
+                            multiplier: Some(7),
+                            unit: None,
+                            value: None,
+                        },
+                    )])),
+                },
+                PalletInstance {
+                    name: "Treasurers".to_string(),
+                    parameters: None,
+                },
+            ],
+        )]);
+        let mut pallets = catalog
+            .apply_configs(
+                &HashMap::new(),
+                &TemplateType::SoloChain,
+                &ProjectOptions {
+                    pallet_instances: instances,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assign_pallet_indices(&mut pallets, &[]).unwrap();
+        let runtime_lib = generate_runtime_lib_file_bytes(
+            &template("SoloChain/runtime/src/lib.rs.hbs"),
+            &pallets,
+        )
+        .unwrap();
+        let runtime_lib = String::from_utf8(runtime_lib).unwrap();
+        assert!(runtime_lib.contains("pub type TechnicalCouncil = pallet_collective<Instance1>;"));
+        assert!(runtime_lib.contains("pub type Treasurers = pallet_collective<Instance2>;"));
+        assert!(
+            runtime_lib.contains("type TechnicalCouncilInstance = pallet_collective::Instance1;")
+        );
+        assert!(
+            runtime_lib.contains("impl pallet_collective::Config<TreasurersInstance> for Runtime")
+        );
+        assert!(runtime_lib.contains("pub const TechnicalCouncilCouncilMaxMembers: u32 = 7;"));
+        assert!(runtime_lib.contains("pub const TreasurersCouncilMaxMembers: u32 = 100;"));
+        assert!(runtime_lib.contains("type MaxMembers = TreasurersCouncilMaxMembers;"));
+
+        let not_instantiable = HashMap::from([(
+            "Pallet Nfts".to_string(),
+            vec![PalletInstance {
+                name: "Collectibles".to_string(),
+                parameters: None,
+            }],
+        )]);
+        assert!(matches!(
+            catalog.apply_configs(
+                &HashMap::new(),
+                &TemplateType::SoloChain,
+                &ProjectOptions {
+                    pallet_instances: not_instantiable,
+                    ..Default::default()
+                },
+            ),
+            Err(CodeGeneratorServiceError::PalletInstanceError(_))
+        ));
+
+        // instance names share the runtime namespace with the template and the other pallets
+        let instance = |name: &str| PalletInstance {
+            name: name.to_string(),
+            parameters: None,
+        };
+        let colliding = [
+            (
+                HashMap::from([("Pallet Collective".to_string(), vec![instance("System")])]),
+                HashMap::new(),
+            ),
+            (
+                HashMap::from([("Pallet Collective".to_string(), vec![instance("Treasury")])]),
+                HashMap::from([("Pallet Treasury".to_string(), None)]),
+            ),
+            (
+                HashMap::from([
+                    ("Pallet Collective".to_string(), vec![instance("Council")]),
+                    ("Pallet Membership".to_string(), vec![instance("Council")]),
+                ]),
+                HashMap::new(),
+            ),
+        ];
+        for (pallet_instances, selected) in colliding {
+            assert!(matches!(
+                catalog.apply_configs(
+                    &selected,
+                    &TemplateType::SoloChain,
+                    &ProjectOptions {
+                        pallet_instances,
+                        ..Default::default()
+                    },
+                ),
+                Err(CodeGeneratorServiceError::PalletInstanceError(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_pallets() {
+        let catalog = catalog().await;
+        let mut custom_pallet = catalog.pallet_configs["Pallet Remark"].clone();
+        custom_pallet.name = "Pallet Template".to_string();
+        custom_pallet.runtime.construct_runtime.runtime = vec![
+            "TemplateModule".to_string(),
+            "pallet_template::Pallet<Runtime>".to_string(),
+        ];
+        custom_pallet.benchmarks = None;
+        let dependency = &mut custom_pallet.dependencies.pallet;
+        dependency.package = "pallet-template".to_string();
+        dependency.alias = "pallet template".to_string();
+        dependency.git_repo = Some("https://github.com/example/pallet-template.git".to_string());
+        dependency.tag = None;
+        dependency.branch = Some("main".to_string());
+
+        let options = ProjectOptions {
+            custom_pallets: vec![custom_pallet.clone()],
+            ..Default::default()
+        };
+        let mut pallets = catalog
+            .apply_configs(&HashMap::new(), &TemplateType::SoloChain, &options)
+            .unwrap();
+        assign_pallet_indices(&mut pallets, &[]).unwrap();
+        let runtime_lib = generate_runtime_lib_file_bytes(
+            &template("SoloChain/runtime/src/lib.rs.hbs"),
+            &pallets,
+        )
+        .unwrap();
+        let runtime_lib = String::from_utf8(runtime_lib).unwrap();
+        assert!(runtime_lib.contains("pub type TemplateModule = pallet_template::Pallet<Runtime>;"));
+        assert!(runtime_lib.contains("impl pallet_template::Config for Runtime"));
+        let manifest = generate_manifest_file_to_bytes(
+            &template("SoloChain/runtime/Cargo.toml.hbs"),
+            &pallets,
+            &SdkSources::new(&TemplateType::SoloChain, &SdkVersion::default()),
+        )
+        .unwrap();
+        assert!(String::from_utf8(manifest).unwrap().contains(
+            "pallet-template = { git = 'https://github.com/example/pallet-template.git', branch = 'main', default-features = false }"
+        ));
+
+        // runtime names of the template and of the selected pallets are taken
+        for (runtime_name, selected) in [("System", None), ("Treasury", Some("Pallet Treasury"))] {
+            let mut colliding = custom_pallet.clone();
+            colliding.runtime.construct_runtime.runtime[0] = runtime_name.to_string();
+            let options = ProjectOptions {
+                custom_pallets: vec![colliding],
+                ..Default::default()
+            };
+            let selected = selected
+                .map(|name| HashMap::from([(name.to_string(), None)]))
+                .unwrap_or_default();
+            assert!(matches!(
+                catalog.apply_configs(&selected, &TemplateType::SoloChain, &options),
+                Err(CodeGeneratorServiceError::CustomPalletError(_))
+            ));
+        }
+
+        custom_pallet.dependencies.pallet.git_repo = None;
+        custom_pallet.dependencies.pallet.branch = None;
+        custom_pallet.dependencies.pallet.version = Some("1.0.0".to_string());
+        let options = ProjectOptions {
+            custom_pallets: vec![custom_pallet],
+            ..Default::default()
+        };
+        assert!(matches!(
+            catalog.apply_configs(&HashMap::new(), &TemplateType::SoloChain, &options),
+            Err(CodeGeneratorServiceError::CustomPalletError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_node_rpc_templates() {
+        let catalog = catalog().await;
+        let mut pallet = catalog.pallet_configs["Pallet Nfts"].clone();
+        pallet.node_rpc = Some(PalletNodeRpcConfig {
+            imports: None,
+            runtime_api_bounds: None,
+            create_full_code: Some(
+                "    module.merge(Nfts::new(client.clone()).into_rpc())?;".to_string(),
+            ),
+            dependencies: None,
+        });
+        assert!(render_pallet_files(
+            &catalog,
+            vec![pallet.clone()],
+            &TemplateType::SoloChain,
+            &SdkVersion::default()
+        )
+        .is_ok_and(|files| files
+            .iter()
+            .any(|(path, _)| path == Path::new("node/src/rpc.rs"))));
+        let Err(CodeGeneratorServiceError::UnsupportedPalletError(unsupported)) =
+            render_pallet_files(
+                &catalog,
+                vec![pallet],
+                &TemplateType::SoloFrontierChain,
+                &SdkVersion::default(),
+            )
+        else {
+            panic!("expected the RPC wiring to be rejected");
+        };
+        assert_eq!(unsupported[0].pallet, "Pallet Nfts");
+    }
+
+    #[tokio::test]
+    async fn test_asset_conversion_rpc() {
+        let cg = code_generator().await;
+        let pallets = HashMap::from([("Pallet Asset Conversion".to_string(), None)]);
+        for template_type in [
+            TemplateType::SoloChain,
+            TemplateType::ParaChain,
+            TemplateType::Minimal,
+            TemplateType::Frontier,
+        ] {
+            let files = cg
+                .preview_project(&pallets, &template_type, &Default::default())
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|(path, content)| (path, String::from_utf8(content).unwrap()))
+                .collect::<HashMap<_, _>>();
+            let node_manifest =
+                toml::from_str::<toml::Table>(&files[Path::new("node/Cargo.toml")]).unwrap();
+            for package in ["pallet-asset-conversion", "frame-support"] {
+                assert!(node_manifest["dependencies"]
+                    .as_table()
+                    .unwrap()
+                    .contains_key(package));
+            }
+            let rpc = &files[Path::new("node/src/rpc.rs")];
+            assert!(rpc.contains("C::Api: pallet_asset_conversion::AssetConversionApi<"));
+            assert!(rpc.contains("\"assetConversion_quotePriceExactTokensForTokens\""));
+            assert!(rpc.contains(".quote_price_exact_tokens_for_tokens("));
+        }
+        assert!(matches!(
+            cg.preview_project(
+                &pallets,
+                &TemplateType::SoloFrontierChain,
+                &Default::default()
+            )
+            .await,
+            Err(CodeGeneratorServiceError::UnsupportedPalletError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pallet_scaffolds() {
+        let catalog = catalog().await;
+        let mut scaffold = kitty_market_scaffold();
+        let options = ProjectOptions {
+            pallet_scaffolds: vec![scaffold.clone()],
+            ..Default::default()
+        };
+        let mut parameters = HashMap::new();
+        parameters.insert(
+            "MaxKitties".to_string(),
+            ParameterConfiguration {
+                multiplier: None,
+                unit: None,
+                value: Some("5".to_string()),
+            },
+        );
+        let mut pallet_configs = HashMap::new();
+        pallet_configs.insert("Pallet KittyMarket".to_string(), Some(parameters));
+        let mut pallets = catalog
+            .apply_configs(&pallet_configs, &TemplateType::SoloChain, &options)
+            .unwrap();
+        assign_pallet_indices(&mut pallets, &[]).unwrap();
+        let runtime_lib = generate_runtime_lib_file_bytes(
+            &template("SoloChain/runtime/src/lib.rs.hbs"),
+            &pallets,
+        )
+        .unwrap();
+        let runtime_lib = String::from_utf8(runtime_lib).unwrap();
+        assert!(
+            runtime_lib.contains("pub type KittyMarket = pallet_kitty_market::Pallet<Runtime>;")
+        );
+        assert!(runtime_lib.contains("pub const KittyMarketMaxKitties: u32 = 5;"));
+        assert!(runtime_lib.contains("type MaxKitties = KittyMarketMaxKitties;"));
+        assert!(runtime_lib.contains("[pallet_kitty_market, KittyMarket]"));
+        let manifest = generate_manifest_file_to_bytes(
+            &template("SoloChain/runtime/Cargo.toml.hbs"),
+            &pallets,
+            &SdkSources::new(&TemplateType::SoloChain, &SdkVersion::default()),
+        )
+        .unwrap();
+        assert!(String::from_utf8(manifest).unwrap().contains(
+            "pallet-kitty-market = { path = '../pallets/kitty_market', default-features = false }"
+        ));
+
+        let mut multi_line = scaffold.clone();
+        multi_line.description = Some("Kitty market\n#![no_std]".to_string());
+        scaffold.name = "KittyMarket".to_string();
+        for scaffold in [scaffold, multi_line] {
+            let options = ProjectOptions {
+                pallet_scaffolds: vec![scaffold],
+                ..Default::default()
+            };
+            assert!(matches!(
+                catalog.apply_configs(&HashMap::new(), &TemplateType::SoloChain, &options),
+                Err(CodeGeneratorServiceError::PalletScaffoldError(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parameter_validation() {
+        let catalog = catalog().await;
+        let configuration = |multiplier: Option<i64>, unit: Option<&str>| ParameterConfiguration {
+            multiplier,
+            unit: unit.map(ToString::to_string),
+            value: None,
+        };
+        let typed = |value: &str| ParameterConfiguration {
+            multiplier: None,
+            unit: None,
+            value: Some(value.to_string()),
+        };
+        let pallets = HashMap::from([
+            (
+                "Pallet Referenda".to_string(),
+                Some(HashMap::from([
+                    (
+                        "SubmissionDeposit".to_string(),
+                        configuration(Some(5), Some("CENTS")),
+                    ),
+                    (
+                        "UndecidingTimeout".to_string(),
+                        configuration(None, Some("WEEKS")),
+                    ),
+                    ("MaxSomething".to_string(), configuration(Some(1), None)),
+                ])),
+            ),
+            (
+                "Pallet Aura".to_string(),
+                Some(HashMap::from([(
+                    "AllowMultipleBlocksPerSlot".to_string(),
+                    configuration(Some(1), Some("true")),
+                )])),
+            ),
+            (
+                "Pallet Asset Rate".to_string(),
+                Some(HashMap::from([(
+                    "Anything".to_string(),
+                    configuration(Some(1), None),
+                )])),
+            ),
+            (
+                "Pallet Treasury".to_string(),
+                Some(HashMap::from([
+                    ("TreasuryBurn".to_string(), typed("100.5")),
+                    ("TreasuryPalletId".to_string(), typed("py/treasury")),
+                ])),
+            ),
+        ]);
+        let result = catalog.apply_configs(&pallets, &TemplateType::SoloChain, &Default::default());
+        let Err(CodeGeneratorServiceError::InvalidParameterConfiguration(invalid_parameters)) =
+            result
+        else {
+            panic!("expected invalid parameters");
+        };
+        let invalid_parameters = invalid_parameters
+            .iter()
+            .map(|invalid| (invalid.pallet.as_str(), invalid.parameter.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            invalid_parameters,
+            vec![
+                ("Pallet Asset Rate", "Anything"),
+                ("Pallet Aura", "AllowMultipleBlocksPerSlot"),
+                ("Pallet Referenda", "MaxSomething"),
+                ("Pallet Referenda", "UndecidingTimeout"),
+                ("Pallet Treasury", "TreasuryBurn"),
+                ("Pallet Treasury", "TreasuryPalletId"),
+            ]
+        );
+
+        let pallets = HashMap::from([
+            (
+                "Pallet Referenda".to_string(),
+                Some(HashMap::from([(
+                    "SubmissionDeposit".to_string(),
+                    configuration(Some(5), Some("CENTS")),
+                )])),
+            ),
+            (
+                "Pallet Treasury".to_string(),
+                Some(HashMap::from([
+                    ("TreasuryBurn".to_string(), typed("12.5")),
+                    ("TreasuryPalletId".to_string(), typed("py/funds")),
+                ])),
+            ),
+        ]);
+        let mut pallets = catalog
+            .apply_configs(&pallets, &TemplateType::SoloChain, &Default::default())
+            .unwrap();
+        let referenda = pallets
+            .iter()
+            .find(|pallet| pallet.name == "Pallet Referenda")
+            .unwrap();
+        let expression = &referenda.runtime.optional_parameter_types.as_ref().unwrap()
+            ["SubmissionDeposit"]
+            .expression
+            .as_ref()
+            .unwrap();
+        assert_eq!(expression.configured_multiplier, Some(5));
+        assert_eq!(expression.configured_unit, Some("CENTS".to_string()));
+        assign_pallet_indices(&mut pallets, &[]).unwrap();
+        let runtime_lib = generate_runtime_lib_file_bytes(
+            &template("SoloChain/runtime/src/lib.rs.hbs"),
+            &pallets,
+        )
+        .unwrap();
+        let runtime_lib = String::from_utf8(runtime_lib).unwrap();
+        assert!(
+            runtime_lib.contains("pub const TreasuryBurn: Permill = Permill::from_parts(125000);")
+        );
+        assert!(runtime_lib
+            .contains("pub const TreasuryPalletId: PalletId = PalletId(*b\"py/funds\");"));
+    }
+
+    #[tokio::test]
+    async fn test_pallet_trait_alternatives() {
+        let catalog = catalog().await;
+        let options = |associated_type: &str, alternative: &str| ProjectOptions {
+            pallet_traits: HashMap::from([(
+                "Pallet Bounties".to_string(),
+                HashMap::from([(associated_type.to_string(), alternative.to_string())]),
+            )]),
+            ..Default::default()
+        };
+        let bounties = HashMap::from([("Pallet Bounties".to_string(), None)]);
+
+        let pallets = catalog
+            .apply_configs(
+                &bounties,
+                &TemplateType::SoloChain,
+                &options("OnSlash", "treasury"),
+            )
+            .unwrap();
+        let pallet = pallets
+            .iter()
+            .find(|pallet| pallet.name == "Pallet Bounties")
+            .unwrap();
+        assert_eq!(pallet.runtime.pallet_traits["OnSlash"], "Treasury");
+        assert_eq!(pallet.runtime.pallet_traits["ChildBountyManager"], "()");
+
+        // pallet-child-bounties has to be requested as well
+        let result = catalog.apply_configs(
+            &bounties,
+            &TemplateType::SoloChain,
+            &options("ChildBountyManager", "child_bounties"),
+        );
+        assert!(matches!(
+            result,
+            Err(CodeGeneratorServiceError::PalletTraitError(_))
+        ));
+        let mut with_child_bounties = bounties.clone();
+        with_child_bounties.insert("Pallet Child Bounties".to_string(), None);
+        assert!(catalog
+            .apply_configs(
+                &with_child_bounties,
+                &TemplateType::SoloChain,
+                &options("ChildBountyManager", "child_bounties"),
+            )
+            .is_ok());
+
+        for (associated_type, alternative) in [("OnSlash", "unknown"), ("WeightInfo", "()")] {
+            assert!(matches!(
+                catalog.apply_configs(
+                    &bounties,
+                    &TemplateType::SoloChain,
+                    &options(associated_type, alternative),
+                ),
+                Err(CodeGeneratorServiceError::PalletTraitError(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conditional_pallet_traits() {
+        let catalog = catalog().await;
+        let render = |pallet_names: &[&str]| {
+            let pallets = pallet_names
+                .iter()
+                .map(|name| (name.to_string(), None))
+                .collect::<HashMap<_, _>>();
+            let mut pallets = catalog
+                .apply_configs(&pallets, &TemplateType::SoloChain, &Default::default())
+                .unwrap();
+            assign_pallet_indices(&mut pallets, &[]).unwrap();
+            let runtime_lib = generate_runtime_lib_file_bytes(
+                &template("SoloChain/runtime/src/lib.rs.hbs"),
+                &pallets,
+            )
+            .unwrap();
+            String::from_utf8(runtime_lib).unwrap()
+        };
+        let runtime_lib = render(&["Pallet Bounties"]);
+        assert!(runtime_lib.contains("type ChildBountyManager = ();"));
+        assert!(!runtime_lib.contains("pallet_collective::EnsureProportionMoreThan"));
+
+        let runtime_lib = render(&[
+            "Pallet Bounties",
+            "Pallet Child Bounties",
+            "Pallet Collective",
+        ]);
+        assert!(runtime_lib.contains("type ChildBountyManager = ChildBounties;"));
+        assert!(runtime_lib.contains(
+            "type SpendOrigin = EnsureWithSuccess<frame_support::traits::EitherOfDiverse<EnsureRoot<AccountId>, pallet_collective::EnsureProportionMoreThan<AccountId, (), 1, 2>>, AccountId, MaxBalance>;"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pallet_conflicts() {
+        let catalog = catalog().await;
+        let contracts = ["Pallet Contracts", "Pallet Contracts Parachain"];
+        assert_eq!(
+            pallet_conflicts(contracts.iter().map(|name| &catalog.pallet_configs[*name])),
+            vec!["Pallet Contracts cannot be used together with Pallet Contracts Parachain"]
+        );
+        let result = catalog.filter_configs(
+            contracts.iter().map(ToString::to_string).collect(),
+            &TemplateType::SoloChain,
+        );
+        assert!(matches!(
+            result,
+            Err(CodeGeneratorServiceError::PalletConflictError(_))
+        ));
+        assert!(catalog
+            .filter_configs(vec![contracts[0].to_string()], &TemplateType::SoloChain)
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_pallets() {
+        let catalog = catalog().await;
+        let result = catalog.filter_configs(
+            vec!["Pallet Contracts Parachain".to_string()],
+            &TemplateType::SoloChain,
+        );
+        let Err(CodeGeneratorServiceError::UnsupportedPalletError(unsupported)) = result else {
+            panic!("expected an unsupported pallet error");
+        };
+        assert_eq!(
+            unsupported,
+            vec![UnsupportedPallet {
+                pallet: "Pallet Contracts Parachain".to_string(),
+                template: TemplateType::SoloChain,
+                required_by: None,
+                suggestion: Some("Pallet Contracts".to_string()),
+            }]
+        );
+        assert!(catalog
+            .filter_configs(
+                vec!["Pallet Contracts Parachain".to_string()],
+                &TemplateType::ParaChain,
+            )
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reload_catalog() {
+        dotenv::from_filename(".env.local").ok();
+        let config_directory = tmpdir::TmpDir::new("configs").await.unwrap();
+        let config_path = config_directory.to_path_buf();
+        for entry in std::fs::read_dir(std::env::var("CONFIG_DIRECTORY").unwrap()).unwrap() {
+            let entry = entry.unwrap();
+            std::fs::copy(entry.path(), config_path.join(entry.file_name())).unwrap();
+        }
+        let cg = CodeGeneratorService {
+            config_directory: config_path.display().to_string(),
+            templates_directory: std::env::var("TEMPLATES_DIRECTORY").unwrap(),
+            catalog: ArcSwap::from_pointee(Catalog::default()),
+            archiver_service: Arc::new(AsyncZipArchiverService),
+        };
+        cg.reload_catalog().await.unwrap();
+        let snapshot = cg.catalog();
+
+        let remark = std::fs::read_to_string(config_path.join("pallet_remark.toml")).unwrap();
+        std::fs::write(
+            config_path.join("pallet_remark_copy.toml"),
+            remark.replace("name = \"Pallet Remark\"", "name = \"Pallet Remark Copy\""),
+        )
+        .unwrap();
+        let reloaded = cg.reload_catalog().await.unwrap();
+        assert!(reloaded.pallet_configs.contains_key("Pallet Remark Copy"));
+        assert!(cg
+            .catalog()
+            .pallet_configs
+            .contains_key("Pallet Remark Copy"));
+        // a generation holding the previous snapshot is not affected
+        assert!(!snapshot.pallet_configs.contains_key("Pallet Remark Copy"));
+
+        std::fs::write(
+            config_path.join("pallet_remark_copy.toml"),
+            remark
+                .replace("name = \"Pallet Remark\"", "name = \"Pallet Remark Copy\"")
+                .replace("[metadata]", "[metadata]\nrequired_pallets = []"),
+        )
+        .unwrap();
+        assert!(matches!(
+            cg.reload_catalog().await,
+            Err(CodeGeneratorServiceError::LoadConfigsError(
+                LoadConfigsError::ParseError(_, _)
+            ))
+        ));
+        std::fs::write(
+            config_path.join("pallet_remark_copy.toml"),
+            remark.replace(
+                "name = \"Pallet Remark\"",
+                "name = \"Pallet Remark Copy\"\n\n[dependencies]\nconflicts_with = [\"Pallet Nope\"]",
+            ),
+        )
+        .unwrap();
+        assert!(matches!(
+            cg.reload_catalog().await,
+            Err(CodeGeneratorServiceError::InvalidCatalogError(_))
+        ));
+        assert!(Arc::ptr_eq(&cg.catalog(), &reloaded));
+    }
+
+    #[tokio::test]
+    async fn test_reload_templates() {
+        dotenv::from_filename(".env.local").ok();
+        let templates_directory = tmpdir::TmpDir::new("templates").await.unwrap();
+        let templates_path = templates_directory.to_path_buf();
+        let mut directories = vec![PathBuf::new()];
+        while let Some(directory) = directories.pop() {
+            let source = Path::new(&std::env::var("TEMPLATES_DIRECTORY").unwrap()).join(&directory);
+            std::fs::create_dir_all(templates_path.join(&directory)).unwrap();
+            for entry in std::fs::read_dir(source).unwrap() {
+                let entry = entry.unwrap();
+                let path = directory.join(entry.file_name());
+                if entry.file_type().unwrap().is_dir() {
+                    directories.push(path);
+                } else {
+                    std::fs::copy(entry.path(), templates_path.join(path)).unwrap();
+                }
+            }
+        }
+        let cg = CodeGeneratorService {
+            config_directory: std::env::var("CONFIG_DIRECTORY").unwrap(),
+            templates_directory: templates_path.display().to_string(),
+            catalog: ArcSwap::from_pointee(Catalog::default()),
+            archiver_service: Arc::new(AsyncZipArchiverService),
+        };
+        let snapshot = cg.reload_catalog().await.unwrap();
+        let render_manifest = |catalog: &Catalog| {
+            let files = render_pallet_files(
+                catalog,
+                vec![],
+                &TemplateType::SoloChain,
+                &SdkVersion::default(),
+            )
+            .unwrap();
+            let (_, manifest) = files
+                .into_iter()
+                .find(|(path, _)| path == Path::new("runtime/Cargo.toml"))
+                .unwrap();
+            String::from_utf8(manifest).unwrap()
+        };
+
+        let manifest_path = templates_path.join("SoloChain/runtime/Cargo.toml.hbs");
+        let manifest = std::fs::read_to_string(&manifest_path).unwrap();
+        std::fs::write(
+            &manifest_path,
+            manifest.replace("[dependencies]", "[dependencies]\nedited = \"1.0.0\""),
+        )
+        .unwrap();
+        // a generation holding the snapshot renders the templates it was loaded with
+        assert!(!render_manifest(&snapshot).contains("edited"));
+        let reloaded = cg.reload_catalog().await.unwrap();
+        assert!(render_manifest(&reloaded).contains("edited = \"1.0.0\""));
+
+        std::fs::remove_file(templates_path.join("SoloChain/node/src/chain_spec.rs.hbs")).unwrap();
+        assert!(!render_manifest(&reloaded).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_generated_files() {
+        let cg = code_generator().await;
+        let catalog = cg.catalog();
+        let mut remark = catalog.pallet_configs["Pallet Remark"].clone();
+        remark.runtime.additional_pallet_impl_code = Some("impl Broken for Runtime {".to_string());
+        let zipper_buffer = AsyncZipArchiverService
+            .archive_folder(Path::new("templates/SoloChain"), HBS_SUFFIX)
+            .await
+            .unwrap();
+        let result = cg
+            .add_pallets_to_archive(
+                zipper_buffer,
+                &catalog,
+                vec![catalog.pallet_configs["Pallet Nfts"].clone(), remark],
+                &TemplateType::SoloChain,
+                &SdkVersion::default(),
+            )
+            .await;
+        let Err(CodeGeneratorServiceError::InvalidGeneratedFileError(invalid_files)) = result
+        else {
+            panic!("expected the runtime not to parse");
+        };
+        assert_eq!(invalid_files.len(), 1);
+        assert_eq!(invalid_files[0].file, "runtime/src/lib.rs");
+        assert!(invalid_files[0].line > 1);
+        assert_eq!(invalid_files[0].pallet.as_deref(), Some("Pallet Remark"));
+    }
+
+    #[tokio::test]
+    async fn test_preview_project() {
+        let cg = code_generator().await;
+        let pallets = HashMap::from([("Pallet Nfts".to_string(), None)]);
+        let files = cg
+            .preview_project(&pallets, &TemplateType::SoloChain, &Default::default())
+            .await
+            .unwrap();
+        let paths = files
+            .iter()
+            .map(|(path, _)| path.display().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "Cargo.toml",
+                "node/Cargo.toml",
+                "node/src/chain_spec.rs",
+                "node/src/rpc.rs",
+                "pallets/template/Cargo.toml",
+                "runtime/Cargo.toml",
+                "runtime/src/lib.rs",
+            ]
+        );
+
+        // the preview is what the archive contains
+        let archive = cg
+            .generate_project_archive(&pallets, &TemplateType::SoloChain, &Default::default())
+            .await
+            .unwrap();
+        let output = tmpdir::TmpDir::new("preview").await.unwrap();
+        cg.unpack_archive_to_folder(archive, &output.to_path_buf())
+            .await
+            .unwrap();
+        for (path, content) in &files {
+            assert_eq!(
+                &std::fs::read(output.to_path_buf().join(path)).unwrap(),
+                content
+            );
+        }
+
+        let pallets = HashMap::from([("Pallet Nope".to_string(), None)]);
+        assert!(matches!(
+            cg.preview_project(&pallets, &TemplateType::SoloChain, &Default::default())
+                .await,
+            Err(CodeGeneratorServiceError::PalletNotFoundError(_))
+        ));
+    }
 }
@@ -0,0 +1,83 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use crate::services::code_generator::types::PalletConfig;
+
+use super::{render_handlebars_template_to_bytes, TemplateRenderError};
+
+#[derive(Debug, Serialize)]
+pub struct GenesisConfigField {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GenesisConfigBlock {
+    pub config_struct_name: String,
+    pub struct_fields: Vec<GenesisConfigField>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChainSpecAggregate {
+    pub additional_chain_spec_code: Vec<String>, // needs to be distinct
+    pub genesis_configs: Vec<GenesisConfigBlock>,
+}
+
+impl From<&[PalletConfig]> for ChainSpecAggregate {
+    fn from(pallets: &[PalletConfig]) -> Self {
+        let mut additional_chain_spec_code = BTreeSet::new(); // makes sure that the code is distinct
+        let mut genesis_configs = vec![];
+        for pallet in pallets {
+            if let Some(code) = pallet.runtime.additional_chain_spec_code.clone() {
+                additional_chain_spec_code.extend(code);
+            }
+            if let Some(genesis_config) = &pallet.runtime.genesis_config {
+                let mut struct_fields = genesis_config
+                    .struct_fields
+                    .iter()
+                    .map(|(name, value)| GenesisConfigField {
+                        name: name.clone(),
+                        value: value.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                struct_fields.sort_by(|a, b| a.name.cmp(&b.name));
+                genesis_configs.push(GenesisConfigBlock {
+                    config_struct_name: genesis_config.config_struct_name.clone(),
+                    struct_fields,
+                });
+            }
+        }
+        ChainSpecAggregate {
+            additional_chain_spec_code: additional_chain_spec_code.into_iter().collect(),
+            genesis_configs,
+        }
+    }
+}
+
+pub fn generate_chain_spec_file_bytes(
+    chain_spec_template: &str,
+    pallet_configs: &[PalletConfig],
+) -> Result<Vec<u8>, TemplateRenderError> {
+    let chain_spec_aggregate = ChainSpecAggregate::from(pallet_configs);
+    render_handlebars_template_to_bytes(chain_spec_template, &chain_spec_aggregate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::code_generator::test_utils::{catalog, template};
+    #[tokio::test]
+    async fn test_chain_spec_genesis_config() {
+        let catalog = catalog().await;
+        let pallets = vec![catalog.pallet_configs["Pallet Assets"].clone()];
+        let chain_spec = generate_chain_spec_file_bytes(
+            &template("SoloChain/node/src/chain_spec.rs.hbs"),
+            &pallets,
+        )
+        .unwrap();
+        let chain_spec = String::from_utf8(chain_spec).unwrap();
+        assert!(chain_spec.contains("\"assets\": {"));
+        assert!(chain_spec.contains("\"assets\": vec![(1u32, root_key.clone(), true, 1u128)],"));
+    }
+}
@@ -1,36 +1,338 @@
+use std::collections::HashSet;
+
 use serde::Serialize;
 
-use crate::services::code_generator::types::PalletConfig;
+use crate::services::code_generator::types::{
+    CargoComplexDependency, CargoSimpleDependency, PalletConfig, RuntimeFeature,
+};
 
-use super::{render_handlebars_template_to_bytes, TemplateRenderError};
+use super::{render_handlebars_template_to_bytes, SdkSources, TemplateRenderError};
 
 #[derive(Debug, Serialize)]
 pub struct ManifestConfig {
     pub name: String,
-    pub git_path: String,
-    pub tag: String,
+    /// Location keys of the dependency, e.g. `git = '...', tag = '...'`
+    pub source: String,
     pub use_default: bool,
+    /// Quoted, comma separated cargo features
+    pub features: Option<String>,
+    pub std: bool,
+    pub runtime_benchmarks: bool,
+    pub try_runtime: bool,
+}
+
+/// Data of a runtime or node manifest template.
+#[derive(Debug, Serialize)]
+struct ManifestAggregate {
+    dependencies: Vec<ManifestConfig>,
+    #[serde(flatten)]
+    sdk_sources: SdkSources,
+}
+
+fn dependency_source(dependency: &CargoComplexDependency) -> String {
+    let mut keys = vec![];
+    if let Some(version) = &dependency.version {
+        keys.push(format!("version = '{}'", version));
+    }
+    if let Some(path) = &dependency.path {
+        keys.push(format!("path = '{}'", path));
+    }
+    if let Some(git_repo) = &dependency.git_repo {
+        keys.push(format!("git = '{}'", git_repo));
+    }
+    for (key, value) in [
+        ("tag", &dependency.tag),
+        ("branch", &dependency.branch),
+        ("rev", &dependency.rev),
+    ] {
+        if let Some(value) = value {
+            keys.push(format!("{} = '{}'", key, value));
+        }
+    }
+    keys.join(", ")
+}
+
+impl From<&CargoComplexDependency> for ManifestConfig {
+    fn from(dependency: &CargoComplexDependency) -> Self {
+        Self {
+            name: dependency.name_cebab_case(),
+            source: dependency_source(dependency),
+            use_default: dependency.default_features,
+            features: dependency.features.as_ref().map(|features| {
+                features
+                    .iter()
+                    .map(|feature| format!("'{}'", feature))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }),
+            std: dependency.has_runtime_feature(RuntimeFeature::Std),
+            runtime_benchmarks: dependency.has_runtime_feature(RuntimeFeature::RuntimeBenchmarks),
+            try_runtime: dependency.has_runtime_feature(RuntimeFeature::TryRuntime),
+        }
+    }
+}
+
+impl From<&CargoSimpleDependency> for ManifestConfig {
+    fn from(dependency: &CargoSimpleDependency) -> Self {
+        Self {
+            name: dependency.package.clone(),
+            source: format!("version = '{}'", dependency.version),
+            use_default: false,
+            features: None,
+            std: dependency.has_runtime_feature(RuntimeFeature::Std),
+            runtime_benchmarks: dependency.has_runtime_feature(RuntimeFeature::RuntimeBenchmarks),
+            try_runtime: dependency.has_runtime_feature(RuntimeFeature::TryRuntime),
+        }
+    }
 }
 
 impl From<&PalletConfig> for ManifestConfig {
     fn from(pallet: &PalletConfig) -> Self {
-        Self {
-            name: pallet.dependencies.pallet.name_cebab_case(),
-            git_path: pallet.dependencies.pallet.git_repo.clone().unwrap(),
-            tag: pallet.dependencies.pallet.tag.clone().unwrap(),
-            use_default: pallet.dependencies.pallet.default_features,
+        (&pallet.dependencies.pallet).into()
+    }
+}
+
+/// Every runtime dependency declared by the pallets, duplicates included.
+fn runtime_dependencies(pallet_configs: &[PalletConfig]) -> Vec<ManifestConfig> {
+    // instances share the crate of their pallet and are deduplicated like any other crate
+    let mut dependencies: Vec<ManifestConfig> =
+        pallet_configs.iter().map(|pallet| pallet.into()).collect();
+    for pallet in pallet_configs {
+        dependencies.extend(
+            pallet
+                .dependencies
+                .additional_pallets
+                .iter()
+                .flatten()
+                .map(ManifestConfig::from),
+        );
+        dependencies.extend(
+            pallet
+                .dependencies
+                .additional_deps
+                .iter()
+                .flatten()
+                .map(ManifestConfig::from),
+        );
+    }
+    dependencies
+}
+
+/// Every node dependency declared by the pallets, duplicates included.
+fn node_dependencies(pallet_configs: &[PalletConfig]) -> Vec<ManifestConfig> {
+    pallet_configs
+        .iter()
+        .filter_map(|pallet| pallet.node_rpc.as_ref())
+        .flat_map(|node_rpc| node_rpc.dependencies.iter().flatten())
+        .map(ManifestConfig::from)
+        .collect()
+}
+
+/// Keeps the first declaration of every crate.
+fn dedup_dependencies(dependencies: Vec<ManifestConfig>) -> Vec<ManifestConfig> {
+    let mut manifest_configs: Vec<ManifestConfig> = vec![];
+    for dependency in dependencies {
+        if !manifest_configs
+            .iter()
+            .any(|existing| existing.name == dependency.name)
+        {
+            manifest_configs.push(dependency);
+        }
+    }
+    manifest_configs
+}
+
+/// Names of the crates that a manifest template declares itself in `[dependencies]`.
+fn template_dependencies(manifest_template: &str) -> HashSet<&str> {
+    let mut in_dependencies = false;
+    let mut names = HashSet::new();
+    for line in manifest_template.lines() {
+        if line.starts_with('[') && line.trim_end().ends_with(']') {
+            in_dependencies = line.trim_end() == "[dependencies]";
+        } else if in_dependencies {
+            // the pallet loops start with `{{`, continuation lines with whitespace or `]`
+            if let Some((key, _)) = line.split_once('=') {
+                let key = key.trim_end();
+                let name = key.strip_suffix(".workspace").unwrap_or(key);
+                if !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                {
+                    names.insert(name);
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Describes every crate that the selected pallets declare with different sources, or that
+/// the runtime or node manifest template already declares.
+pub fn dependency_conflicts(
+    pallet_configs: &[PalletConfig],
+    runtime_manifest_template: &str,
+    node_manifest_template: &str,
+) -> Vec<String> {
+    let mut conflicts = vec![];
+    for (dependencies, manifest_template) in [
+        (
+            runtime_dependencies(pallet_configs),
+            runtime_manifest_template,
+        ),
+        (node_dependencies(pallet_configs), node_manifest_template),
+    ] {
+        let template_dependencies = template_dependencies(manifest_template);
+        for (index, dependency) in dependencies.iter().enumerate() {
+            if template_dependencies.contains(dependency.name.as_str()) {
+                let message = format!("{} is already declared by the template", dependency.name);
+                if !conflicts.contains(&message) {
+                    conflicts.push(message);
+                }
+                continue;
+            }
+            let conflict = dependencies[..index].iter().find(|earlier| {
+                earlier.name == dependency.name && earlier.source != dependency.source
+            });
+            if let Some(earlier) = conflict {
+                let message = format!(
+                    "{} is required both as {{ {} }} and as {{ {} }}",
+                    dependency.name, earlier.source, dependency.source
+                );
+                if !conflicts.contains(&message) {
+                    conflicts.push(message);
+                }
+            }
         }
     }
+    conflicts
 }
 
 pub fn generate_manifest_file_to_bytes(
-    mainfest_file_path: &str,
+    manifest_template: &str,
     pallet_configs: &[PalletConfig],
+    sdk_sources: &SdkSources,
 ) -> Result<Vec<u8>, TemplateRenderError> {
-    let manifest_configs: Vec<ManifestConfig> = pallet_configs
-        .iter()
-        .filter(|pallet| !pallet.metadata.is_instance.unwrap_or_default())
-        .map(|pallet| pallet.into())
-        .collect();
-    render_handlebars_template_to_bytes(mainfest_file_path, &manifest_configs)
+    let aggregate = ManifestAggregate {
+        dependencies: dedup_dependencies(runtime_dependencies(pallet_configs)),
+        sdk_sources: sdk_sources.clone(),
+    };
+    render_handlebars_template_to_bytes(manifest_template, &aggregate)
+}
+
+pub fn generate_node_manifest_file_to_bytes(
+    manifest_template: &str,
+    pallet_configs: &[PalletConfig],
+    sdk_sources: &SdkSources,
+) -> Result<Vec<u8>, TemplateRenderError> {
+    let aggregate = ManifestAggregate {
+        dependencies: dedup_dependencies(node_dependencies(pallet_configs)),
+        sdk_sources: sdk_sources.clone(),
+    };
+    render_handlebars_template_to_bytes(manifest_template, &aggregate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::code_generator::{
+        test_utils::{catalog, template},
+        types::{SdkVersion, TemplateType},
+    };
+    #[tokio::test]
+    async fn test_additional_dependencies() {
+        let catalog = catalog().await;
+        let runtime_template = template("SoloChain/runtime/Cargo.toml.hbs");
+        let node_template = template("SoloChain/node/Cargo.toml.hbs");
+        let hex_literal = |version: &str, runtime_features| CargoSimpleDependency {
+            package: "hex-literal".to_string(),
+            version: version.to_string(),
+            runtime_features,
+        };
+        let mut nfts = catalog.pallet_configs["Pallet Nfts"].clone();
+        nfts.dependencies.additional_deps = Some(vec![hex_literal("0.4.1", None)]);
+        let mut uniques = catalog.pallet_configs["Pallet Uniques"].clone();
+        uniques.dependencies.additional_deps = nfts.dependencies.additional_deps.clone();
+        let mut pallets = vec![nfts, uniques];
+        assert!(dependency_conflicts(&pallets, &runtime_template, &node_template).is_empty());
+        let manifest = generate_manifest_file_to_bytes(
+            &template("SoloChain/runtime/Cargo.toml.hbs"),
+            &pallets,
+            &SdkSources::new(&TemplateType::SoloChain, &SdkVersion::default()),
+        )
+        .unwrap();
+        let manifest = String::from_utf8(manifest).unwrap();
+        assert_eq!(
+            manifest
+                .matches("hex-literal = { version = '0.4.1', default-features = false }")
+                .count(),
+            1
+        );
+        // hex-literal has no std feature, it is only forwarded when asked for
+        assert!(!manifest.contains("\"hex-literal/std\","));
+        pallets[0].dependencies.additional_deps =
+            Some(vec![hex_literal("0.4.1", Some(vec![RuntimeFeature::Std]))]);
+        let manifest = generate_manifest_file_to_bytes(
+            &template("SoloChain/runtime/Cargo.toml.hbs"),
+            &pallets,
+            &SdkSources::new(&TemplateType::SoloChain, &SdkVersion::default()),
+        )
+        .unwrap();
+        assert!(String::from_utf8(manifest)
+            .unwrap()
+            .contains("\"hex-literal/std\","));
+
+        pallets[1].dependencies.additional_deps = Some(vec![hex_literal("0.3.4", None)]);
+        assert_eq!(
+            dependency_conflicts(&pallets, &runtime_template, &node_template),
+            vec!["hex-literal is required both as { version = '0.4.1' } and as { version = '0.3.4' }"]
+        );
+
+        pallets[1].dependencies.additional_deps = Some(vec![CargoSimpleDependency {
+            package: "sp-core".to_string(),
+            version: "34.0.0".to_string(),
+            runtime_features: None,
+        }]);
+        assert_eq!(
+            dependency_conflicts(&pallets[1..], &runtime_template, &node_template),
+            vec!["sp-core is already declared by the template"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dependency_sources() {
+        let catalog = catalog().await;
+        let mut pallet = catalog.pallet_configs["Pallet Nfts"].clone();
+        let dependency = &mut pallet.dependencies.pallet;
+        dependency.branch = Some("stable2407".to_string());
+        assert!(dependency.validate().is_err());
+        dependency.tag = None;
+        dependency.features = Some(vec!["experimental".to_string()]);
+        assert!(dependency.validate().is_ok());
+        let manifest = generate_manifest_file_to_bytes(
+            &template("SoloChain/runtime/Cargo.toml.hbs"),
+            &[pallet.clone()],
+            &SdkSources::new(&TemplateType::SoloChain, &SdkVersion::default()),
+        )
+        .unwrap();
+        assert!(String::from_utf8(manifest).unwrap().contains(
+            "pallet-nfts = { git = 'https://github.com/paritytech/polkadot-sdk.git', branch = 'stable2407', default-features = false, features = ['experimental'] }"
+        ));
+
+        let dependency = &mut pallet.dependencies.pallet;
+        dependency.git_repo = None;
+        dependency.branch = None;
+        assert!(dependency.validate().is_err());
+        dependency.version = Some("32.0.0".to_string());
+        assert!(dependency.validate().is_ok());
+        let manifest = generate_manifest_file_to_bytes(
+            &template("SoloChain/runtime/Cargo.toml.hbs"),
+            &[pallet],
+            &SdkSources::new(&TemplateType::SoloChain, &SdkVersion::default()),
+        )
+        .unwrap();
+        assert!(String::from_utf8(manifest).unwrap().contains(
+            "pallet-nfts = { version = '32.0.0', default-features = false, features = ['experimental'] }"
+        ));
+    }
 }
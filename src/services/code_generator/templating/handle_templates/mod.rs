@@ -1,10 +1,17 @@
+pub mod chain_spec;
 pub mod manifest;
+pub mod node_rpc;
+pub mod pallet_scaffold;
 pub mod runtime_lib;
 
 use handlebars;
 use serde::Serialize;
 use thiserror::Error;
 
+use crate::services::code_generator::types::{
+    SdkVersion, TemplateType, FRONTIER_REPO, POLKADOT_SDK_REPO,
+};
+
 const TEMPLATE: &str = "template";
 pub const HBS_SUFFIX: &str = "hbs";
 
@@ -18,16 +25,52 @@ pub enum TemplateRenderError {
     FileOperationError(#[from] std::io::Error),
     #[error("Failed to render template: {0}")]
     RenderError(#[from] handlebars::RenderError),
+    #[error("Pallet {0} has no pallet index")]
+    MissingPalletIndex(String),
+    #[error("Template file {0} is missing")]
+    MissingTemplateFile(String),
+}
+
+/// Location keys of the polkadot-sdk and Frontier crates, rendered into every manifest so all of
+/// them follow the selected release.
+#[derive(Debug, Clone, Serialize)]
+pub struct SdkSources {
+    pub polkadot_sdk: String,
+    pub frontier: String,
+}
+
+impl SdkSources {
+    /// The Frontier templates follow the stable branch, the others pin the release tag.
+    pub fn new(template_type: &TemplateType, sdk_version: &SdkVersion) -> Self {
+        let polkadot_sdk = if template_type.follows_stable_branch() {
+            format!(
+                "git = '{}', branch = '{}'",
+                POLKADOT_SDK_REPO,
+                sdk_version.stable_branch()
+            )
+        } else {
+            format!("git = '{}', tag = '{}'", POLKADOT_SDK_REPO, sdk_version)
+        };
+        Self {
+            polkadot_sdk,
+            frontier: format!(
+                "git = '{}', branch = '{}'",
+                FRONTIER_REPO,
+                sdk_version.stable_branch()
+            ),
+        }
+    }
 }
 
+/// Renders the contents of a `.hbs` file, taken from the catalog rather than read from disk.
 pub fn render_handlebars_template_to_bytes<T>(
-    template_path: &str,
+    template: &str,
     data: &T,
 ) -> Result<Vec<u8>, TemplateRenderError>
 where
     T: Serialize,
 {
     let mut handlebars = handlebars::Handlebars::new();
-    handlebars.register_template_file(TEMPLATE, template_path)?;
+    handlebars.register_template_string(TEMPLATE, template)?;
     Ok(handlebars.render(TEMPLATE, data)?.into_bytes())
 }
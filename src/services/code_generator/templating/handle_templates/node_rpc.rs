@@ -0,0 +1,101 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+use crate::services::code_generator::types::PalletConfig;
+
+use super::{render_handlebars_template_to_bytes, TemplateRenderError};
+
+#[derive(Debug, Serialize)]
+pub struct NodeRpcAggregate {
+    pub imports: Vec<String>,            // needs to be distinct
+    pub runtime_api_bounds: Vec<String>, // needs to be distinct
+    pub create_full_code: Vec<String>,
+}
+
+impl From<&[PalletConfig]> for NodeRpcAggregate {
+    fn from(pallets: &[PalletConfig]) -> Self {
+        let mut imports = BTreeSet::new();
+        let mut runtime_api_bounds = BTreeSet::new();
+        let mut create_full_code = vec![];
+        for node_rpc in pallets.iter().filter_map(|pallet| pallet.node_rpc.as_ref()) {
+            imports.extend(node_rpc.imports.clone().unwrap_or_default());
+            runtime_api_bounds.extend(node_rpc.runtime_api_bounds.clone().unwrap_or_default());
+            if let Some(code) = &node_rpc.create_full_code {
+                create_full_code.push(code.clone());
+            }
+        }
+        NodeRpcAggregate {
+            imports: imports.into_iter().collect(),
+            runtime_api_bounds: runtime_api_bounds.into_iter().collect(),
+            create_full_code,
+        }
+    }
+}
+
+pub fn generate_node_rpc_file_bytes(
+    rpc_template: &str,
+    pallet_configs: &[PalletConfig],
+) -> Result<Vec<u8>, TemplateRenderError> {
+    let node_rpc_aggregate = NodeRpcAggregate::from(pallet_configs);
+    render_handlebars_template_to_bytes(rpc_template, &node_rpc_aggregate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::code_generator::{
+        templating::handle_templates::{
+            manifest::generate_node_manifest_file_to_bytes, SdkSources,
+        },
+        test_utils::{catalog, template},
+        types::{CargoComplexDependency, PalletNodeRpcConfig, SdkVersion, TemplateType},
+    };
+    #[tokio::test]
+    async fn test_node_rpc_code() {
+        let catalog = catalog().await;
+        let mut pallet = catalog.pallet_configs["Pallet Nfts"].clone();
+        pallet.node_rpc = Some(PalletNodeRpcConfig {
+            imports: Some(vec![
+                "use pallet_nfts_rpc::{Nfts, NftsApiServer};".to_string()
+            ]),
+            runtime_api_bounds: Some(vec![
+                "pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32>".to_string(),
+            ]),
+            create_full_code: Some(
+                "    module.merge(Nfts::new(client.clone()).into_rpc())?;".to_string(),
+            ),
+            dependencies: Some(vec![CargoComplexDependency {
+                package: "pallet-nfts-rpc".to_string(),
+                version: None,
+                alias: "pallet nfts rpc".to_string(),
+                default_features: true,
+                git_repo: Some("https://github.com/paritytech/polkadot-sdk.git".to_string()),
+                tag: Some("polkadot-v1.14.0".to_string()),
+                branch: None,
+                rev: None,
+                path: None,
+                features: None,
+                runtime_features: None,
+            }]),
+        });
+        let pallets = vec![pallet];
+        let rpc =
+            generate_node_rpc_file_bytes(&template("SoloChain/node/src/rpc.rs.hbs"), &pallets)
+                .unwrap();
+        let rpc = String::from_utf8(rpc).unwrap();
+        assert!(rpc.contains("use pallet_nfts_rpc::{Nfts, NftsApiServer};"));
+        assert!(
+            rpc.contains("C::Api: pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32>,")
+        );
+        assert!(rpc.contains("module.merge(Nfts::new(client.clone()).into_rpc())?;"));
+        let manifest = generate_node_manifest_file_to_bytes(
+            &template("SoloChain/node/Cargo.toml.hbs"),
+            &pallets,
+            &SdkSources::new(&TemplateType::SoloChain, &SdkVersion::default()),
+        )
+        .unwrap();
+        let manifest = String::from_utf8(manifest).unwrap();
+        assert!(manifest.contains("pallet-nfts-rpc = { git = 'https://github.com/paritytech/polkadot-sdk.git', tag = 'polkadot-v1.14.0', default-features = true }"));
+    }
+}
@@ -0,0 +1,199 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::services::code_generator::types::{
+    PalletScaffold, PalletScaffoldCall, PalletScaffoldConstant, PalletScaffoldEvent,
+    PalletScaffoldStorage, SdkVersion, TemplateType,
+};
+
+use super::{render_handlebars_template_to_bytes, SdkSources, TemplateRenderError, HBS_SUFFIX};
+
+pub const PALLET_SCAFFOLD_DIRECTORY: &str = "scaffolds/pallet";
+const PALLET_SCAFFOLD_FILES: [&str; 5] = [
+    "Cargo.toml",
+    "src/lib.rs",
+    "src/mock.rs",
+    "src/tests.rs",
+    "src/benchmarking.rs",
+];
+
+#[derive(Debug, Serialize)]
+pub struct PalletScaffoldAggregate {
+    /// Directory under `pallets/`, also the snake_case part of the crate name
+    pub name: String,
+    pub crate_name: String,
+    pub module_name: String,
+    /// UpperCamelCase name used in `construct_runtime!`
+    pub runtime_name: String,
+    /// Single line, rendered into the crate docs
+    pub description: String,
+    /// `description` as a quoted TOML string for the crate manifest
+    pub manifest_description: String,
+    /// Location keys of polkadot-sdk crates, matching the ones of the template
+    pub polkadot_sdk: String,
+    pub storage: Vec<PalletScaffoldStorage>,
+    pub calls: Vec<PalletScaffoldCall>,
+    pub events: Vec<PalletScaffoldEvent>,
+    pub errors: Vec<String>,
+    pub constants: Vec<PalletScaffoldConstant>,
+}
+
+/// `pallet_kitty_market` -> `KittyMarket`
+pub fn scaffold_runtime_name(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| part[..1].to_uppercase() + &part[1..])
+        .collect()
+}
+
+/// Quoted TOML string with its quotes and backslashes escaped.
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+impl PalletScaffoldAggregate {
+    pub fn new(
+        scaffold: &PalletScaffold,
+        template_type: &TemplateType,
+        sdk_version: &SdkVersion,
+    ) -> Self {
+        let description = scaffold
+            .description
+            .clone()
+            .unwrap_or_else(|| format!("FRAME pallet {}", scaffold.name));
+        Self {
+            name: scaffold.name.clone(),
+            crate_name: format!("pallet-{}", scaffold.name.replace('_', "-")),
+            module_name: format!("pallet_{}", scaffold.name),
+            runtime_name: scaffold_runtime_name(&scaffold.name),
+            manifest_description: toml_string(&description),
+            description,
+            polkadot_sdk: SdkSources::new(template_type, sdk_version).polkadot_sdk,
+            storage: scaffold.storage.clone().unwrap_or_default(),
+            calls: scaffold.calls.clone().unwrap_or_default(),
+            events: scaffold.events.clone().unwrap_or_default(),
+            errors: scaffold.errors.clone().unwrap_or_default(),
+            constants: scaffold.constants.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Renders the crate of a scaffolded pallet from the `.hbs` files of
+/// [`PALLET_SCAFFOLD_DIRECTORY`], paths are relative to the project root.
+pub fn generate_pallet_scaffold_files(
+    scaffold: &PalletScaffold,
+    scaffold_files: &HashMap<PathBuf, String>,
+    template_type: &TemplateType,
+    sdk_version: &SdkVersion,
+) -> Result<Vec<(PathBuf, Vec<u8>)>, TemplateRenderError> {
+    let aggregate = PalletScaffoldAggregate::new(scaffold, template_type, sdk_version);
+    PALLET_SCAFFOLD_FILES
+        .iter()
+        .map(|file| {
+            let template_path = format!("{}.{}", file, HBS_SUFFIX);
+            let template = scaffold_files
+                .get(Path::new(&template_path))
+                .ok_or_else(|| {
+                    TemplateRenderError::MissingTemplateFile(format!(
+                        "{}/{}",
+                        PALLET_SCAFFOLD_DIRECTORY, template_path
+                    ))
+                })?;
+            let content = render_handlebars_template_to_bytes(template, &aggregate)?;
+            Ok((
+                PathBuf::from("pallets").join(&aggregate.name).join(file),
+                content,
+            ))
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+struct WorkspaceManifestAggregate {
+    /// Extra workspace members, one per scaffolded pallet
+    members: Vec<String>,
+    #[serde(flatten)]
+    sdk_sources: SdkSources,
+}
+
+/// Renders the workspace manifest with the scaffolded pallets as extra members.
+pub fn generate_workspace_manifest_file_bytes(
+    manifest_template: &str,
+    scaffolds: &[PalletScaffold],
+    sdk_sources: &SdkSources,
+) -> Result<Vec<u8>, TemplateRenderError> {
+    let aggregate = WorkspaceManifestAggregate {
+        members: scaffolds
+            .iter()
+            .map(|scaffold| format!("pallets/{}", scaffold.name))
+            .collect(),
+        sdk_sources: sdk_sources.clone(),
+    };
+    render_handlebars_template_to_bytes(manifest_template, &aggregate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::services::code_generator::test_utils::{catalog, kitty_market_scaffold, template};
+
+    #[tokio::test]
+    async fn test_pallet_scaffold_files() {
+        let catalog = catalog().await;
+        let scaffold = kitty_market_scaffold();
+        let files = generate_pallet_scaffold_files(
+            &scaffold,
+            &catalog.pallet_scaffold_files,
+            &TemplateType::SoloChain,
+            &SdkVersion::default(),
+        )
+        .unwrap();
+        let files = files
+            .into_iter()
+            .map(|(path, content)| (path, String::from_utf8(content).unwrap()))
+            .collect::<HashMap<_, _>>();
+        let lib = &files[Path::new("pallets/kitty_market/src/lib.rs")];
+        assert!(lib
+            .contains("pub type Prices<T: Config> = StorageMap<_, Blake2_128Concat, u32, u128>;"));
+        assert!(lib.contains("pub fn set_price(origin: OriginFor<T>, kitty: u32, price: u128)"));
+        assert!(lib.contains("PriceSet { kitty: u32, },"));
+        assert!(lib.contains("type MaxKitties: Get<u32>;"));
+        assert!(files[Path::new("pallets/kitty_market/Cargo.toml")]
+            .contains("name = \"pallet-kitty-market\""));
+        let mut quoted = scaffold.clone();
+        quoted.description = Some(r#"Sells "kitties" \ trades them"#.to_string());
+        let quoted_files = generate_pallet_scaffold_files(
+            &quoted,
+            &catalog.pallet_scaffold_files,
+            &TemplateType::SoloChain,
+            &SdkVersion::default(),
+        )
+        .unwrap();
+        let manifest = String::from_utf8(quoted_files[0].1.clone()).unwrap();
+        let manifest = toml::from_str::<toml::Table>(&manifest).unwrap();
+        assert_eq!(
+            manifest["package"]["description"].as_str(),
+            quoted.description.as_deref()
+        );
+        assert!(String::from_utf8(quoted_files[1].1.clone())
+            .unwrap()
+            .starts_with(r#"//! Sells "kitties" \ trades them"#));
+        assert!(
+            files[Path::new("pallets/kitty_market/src/benchmarking.rs")].contains("fn set_price()")
+        );
+        let workspace_manifest = generate_workspace_manifest_file_bytes(
+            &template("SoloChain/Cargo.toml.hbs"),
+            &[scaffold],
+            &SdkSources::new(&TemplateType::SoloChain, &SdkVersion::default()),
+        )
+        .unwrap();
+        assert!(String::from_utf8(workspace_manifest)
+            .unwrap()
+            .contains("\"pallets/kitty_market\","));
+    }
+}
@@ -1,11 +1,15 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
 
-use crate::services::code_generator::types::PalletConfig;
+use crate::services::code_generator::types::{PalletConfig, ParameterType};
 use dyn_fmt::AsStrFormatExt;
 use serde::Serialize;
 
 use super::{render_handlebars_template_to_bytes, TemplateRenderError};
 
+/// Index handed out to the first pallet that has no explicitly configured index.
+pub const FIRST_PALLET_INDEX: u8 = 55;
+const PALLET_INDEX_ATTRIBUTE: &str = "#[runtime::pallet_index(";
+
 #[derive(Debug, Serialize)]
 pub struct RuntimeImplBlocks {
     pub additional_pallet_impl_code: Option<String>,
@@ -22,66 +26,178 @@ pub struct RuntimeLibAggregate {
     pub additional_runtime_lib_code: Vec<String>, // needs to be distinct
     pub impl_blocks: Vec<RuntimeImplBlocks>,
     pub construct_runtime: Vec<String>,
+    /// Sorted and distinct, rendered at the top of `apis.rs`
+    pub runtime_api_imports: Vec<String>,
+    pub runtime_api_code: Vec<String>,
+    pub benchmarks: Vec<String>,
 }
 
-fn transform_name(input: &str) -> (String, String, String) {
+/// Instance name of catalog instances such as "GeneralCouncil Collective".
+fn legacy_instance_name(input: &str) -> String {
     let parts: Vec<&str> = input.split_whitespace().collect();
 
     if parts.len() == 2 {
-        let part1 = parts[0].to_string(); // "GeneralCouncil"
-        let part2 = format!("pallet_{}", parts[1].to_lowercase()); // "pallet_collective"
-        let combined = format!("{}{}", part1, parts[1]); // "GeneralCouncilCollective"
-        (part1, part2, combined)
+        parts[0].to_string() // "GeneralCouncil"
+    } else {
+        String::new()
+    }
+}
+
+/// Name the pallet is declared under in the runtime, `pub type <alias> = ...`.
+pub fn runtime_alias(pallet: &PalletConfig) -> String {
+    let construct_runtime = &pallet.runtime.construct_runtime;
+    if pallet.metadata.is_instance.unwrap_or_default() {
+        construct_runtime
+            .instance_name
+            .clone()
+            .unwrap_or_else(|| legacy_instance_name(&pallet.name))
     } else {
-        (String::new(), String::new(), String::new())
+        construct_runtime.runtime[0].clone()
     }
 }
 
-impl From<Vec<PalletConfig>> for RuntimeLibAggregate {
-    fn from(pallets: Vec<PalletConfig>) -> Self {
+/// `pallet_traits` of a pallet with the `when` blocks of the pallets present in the
+/// runtime applied on top, in pallet name order.
+pub fn resolve_pallet_traits(
+    pallet: &PalletConfig,
+    pallet_names: &HashSet<&str>,
+) -> HashMap<String, String> {
+    let mut pallet_traits = pallet.runtime.pallet_traits.clone();
+    let mut conditions = pallet
+        .runtime
+        .when
+        .iter()
+        .flatten()
+        .filter(|(name, _)| pallet_names.contains(name.as_str()))
+        .collect::<Vec<_>>();
+    conditions.sort_by(|a, b| a.0.cmp(b.0));
+    for (_, condition) in conditions {
+        pallet_traits.extend(condition.pallet_traits.clone().unwrap_or_default());
+    }
+    pallet_traits
+}
+
+/// Right hand side of a parameter type: the typed value when there is one,
+/// otherwise the unit and multiplier filled into the format string.
+pub fn parameter_type_expression(parameter_type: &ParameterType) -> String {
+    if let Some(value) = &parameter_type.value {
+        return value.to_rust(&parameter_type.p_type);
+    }
+    let Some(expression) = parameter_type.expression.clone() else {
+        return String::new();
+    };
+    let m = match expression.configured_multiplier {
+        Some(v) => v.to_string(),
+        None => match expression.default_multiplier {
+            Some(v) => v.to_string(),
+            None => "".to_string(),
+        },
+    };
+    let u = match expression.configured_unit {
+        Some(u) => u,
+        None => expression.default_unit,
+    };
+    expression.format.format(&[u, m])
+}
+
+/// Returns the pallet indices hard-coded in a runtime lib template.
+pub fn template_pallet_indices(template: &str) -> Vec<u8> {
+    template
+        .split(PALLET_INDEX_ATTRIBUTE)
+        .skip(1)
+        .filter_map(|rest| rest.split(')').next())
+        .filter_map(|index| index.trim().parse().ok())
+        .collect()
+}
+
+/// Returns the runtime aliases of the pallets hard-coded in a runtime lib template.
+pub fn template_runtime_aliases(template: &str) -> Vec<String> {
+    template
+        .split(PALLET_INDEX_ATTRIBUTE)
+        .skip(1)
+        .filter_map(|rest| rest.split_once("pub type"))
+        .filter_map(|(_, rest)| rest.split('=').next())
+        .map(|alias| alias.trim().to_string())
+        .collect()
+}
+
+impl TryFrom<Vec<PalletConfig>> for RuntimeLibAggregate {
+    type Error = TemplateRenderError;
+
+    fn try_from(pallets: Vec<PalletConfig>) -> Result<Self, Self::Error> {
         let mut additional_runtime_lib_code = HashSet::new(); // makes sure that the code is distinct
         let mut impl_blocks = vec![];
         let mut construct_runtime = vec![];
+        let mut runtime_api_imports = BTreeSet::new();
+        let mut runtime_api_code = vec![];
+        let mut benchmarks = vec![];
 
-        let mut instance_counter = 0;
-        for (index, pallet) in pallets.iter().enumerate() {
+        let pallet_names = pallets
+            .iter()
+            .map(|pallet| pallet.name.as_str())
+            .collect::<HashSet<_>>();
+        // instances are numbered per pallet crate
+        let mut instance_counters: HashMap<String, u8> = HashMap::new();
+        for pallet in pallets.iter() {
             // add the pallet runtime code
             if let Some(code) = pallet.runtime.additional_runtime_lib_code.clone() {
                 for line in code {
                     additional_runtime_lib_code.insert(line);
                 }
             };
+            if let Some(code) = pallet.runtime.runtime_api_code.clone() {
+                runtime_api_code.push(code);
+                runtime_api_imports.extend(
+                    pallet
+                        .runtime
+                        .runtime_api_imports
+                        .clone()
+                        .into_iter()
+                        .flatten(),
+                );
+            }
 
+            let pallet_index = pallet
+                .runtime
+                .construct_runtime
+                .pallet_index
+                .ok_or_else(|| TemplateRenderError::MissingPalletIndex(pallet.name.clone()))?;
             let is_instance = pallet.metadata.is_instance.unwrap_or_default();
-            let (part1, pallet_name, together) = transform_name(&pallet.name);
+            let pallet_name = pallet
+                .dependencies
+                .pallet
+                .alias
+                .clone()
+                .to_lowercase()
+                .replace(" ", "_");
+            let instance_name = runtime_alias(pallet);
+            let mut instance_counter = 0;
             if is_instance {
-                instance_counter += 1;
+                let counter = instance_counters.entry(pallet_name.clone()).or_default();
+                *counter += 1;
+                instance_counter = *counter;
                 construct_runtime.push(format!(
                     "\n\t#[runtime::pallet_index({})]\n\tpub type {} = {}<Instance{}>;",
-                    index + 55,
-                    part1,
-                    pallet_name,
-                    instance_counter
+                    pallet_index, instance_name, pallet_name, instance_counter
                 ));
             } else {
                 construct_runtime.push(format!(
                     "\n\t#[runtime::pallet_index({})]\n\tpub type {} = {};",
-                    index + 55,
+                    pallet_index,
                     pallet.runtime.construct_runtime.runtime[0],
                     pallet.runtime.construct_runtime.runtime[1],
                 ));
             }
+            if let Some(bench) = &pallet.benchmarks {
+                let runtime_type = match &bench.runtime_type {
+                    Some(runtime_type) => runtime_type.clone(),
+                    None if is_instance => instance_name.clone(),
+                    None => pallet.runtime.construct_runtime.runtime[0].clone(),
+                };
+                benchmarks.push(format!("[{}, {}]", bench.module, runtime_type));
+            }
             let additional_pallet_impl_code = pallet.runtime.additional_pallet_impl_code.clone();
-            let pallet_name = pallet
-                .dependencies
-                .pallet
-                .alias
-                .clone()
-                .to_lowercase()
-                .replace(" ", "_");
-            let pallet_traits = pallet
-                .runtime
-                .pallet_traits
+            let pallet_traits = resolve_pallet_traits(pallet, &pallet_names)
                 .iter()
                 .map(|(key, value)| format!("\ttype {} = {};\n", key, value))
                 .collect::<Vec<_>>();
@@ -96,23 +212,12 @@ impl From<Vec<PalletConfig>> for RuntimeLibAggregate {
             {
                 let mut temp = vec![];
                 for (_, pt) in optional_parameter_types {
-                    let m = match pt.expression.configured_multiplier {
-                        Some(v) => v.to_string(),
-                        None => match pt.expression.default_multiplier {
-                            Some(v) => v.to_string(),
-                            None => "".to_string(),
-                        },
-                    };
-                    let u = match pt.expression.configured_unit {
-                        Some(u) => u,
-                        None => pt.expression.default_unit,
-                    };
                     let s = format!(
                         "    pub{}{}: {} = {};",
                         pt.prefix,
                         pt.name,
                         pt.p_type,
-                        pt.expression.format.format(&[u, m])
+                        parameter_type_expression(&pt)
                     );
                     temp.push(s);
                 }
@@ -132,23 +237,89 @@ impl From<Vec<PalletConfig>> for RuntimeLibAggregate {
                 // is_instance
                 is_instance,
                 // type alias_name = pallet_collective::Instance1
-                alias_name: together,
+                alias_name: format!("{}Instance", instance_name),
                 // index of pallet
                 instance_counter,
             });
         }
-        RuntimeLibAggregate {
+        Ok(RuntimeLibAggregate {
             additional_runtime_lib_code: additional_runtime_lib_code.into_iter().collect(),
             impl_blocks,
             construct_runtime,
-        }
+            runtime_api_imports: runtime_api_imports.into_iter().collect(),
+            runtime_api_code,
+            benchmarks,
+        })
     }
 }
 
 pub fn generate_runtime_lib_file_bytes(
-    runtime_lib_file_path: &str,
+    runtime_lib_template: &str,
     pallet_configs: &[PalletConfig],
 ) -> Result<Vec<u8>, TemplateRenderError> {
-    let runtime_lib_aggregate: RuntimeLibAggregate = pallet_configs.to_vec().into();
-    render_handlebars_template_to_bytes(runtime_lib_file_path, &runtime_lib_aggregate)
+    let runtime_lib_aggregate = RuntimeLibAggregate::try_from(pallet_configs.to_vec())?;
+    render_handlebars_template_to_bytes(runtime_lib_template, &runtime_lib_aggregate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::code_generator::{
+        templating::handle_templates::{manifest::generate_manifest_file_to_bytes, SdkSources},
+        test_utils::{catalog, template},
+        types::{SdkVersion, TemplateType},
+    };
+    #[tokio::test]
+    async fn test_runtime_api_code() {
+        let catalog = catalog().await;
+        let mut pallets = vec![catalog.pallet_configs["Pallet Nfts"].clone()];
+        assert!(matches!(
+            generate_runtime_lib_file_bytes(
+                &template("SoloChain/runtime/src/lib.rs.hbs"),
+                &pallets
+            ),
+            Err(TemplateRenderError::MissingPalletIndex(_))
+        ));
+        pallets[0].runtime.construct_runtime.pallet_index = Some(FIRST_PALLET_INDEX);
+        let runtime_lib = generate_runtime_lib_file_bytes(
+            &template("SoloChain/runtime/src/lib.rs.hbs"),
+            &pallets,
+        )
+        .unwrap();
+        let runtime_lib = String::from_utf8(runtime_lib).unwrap();
+        assert!(runtime_lib.contains(
+            "impl pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32> for Runtime"
+        ));
+        assert!(runtime_lib.contains("[pallet_nfts, Nfts]"));
+        let benchmarks = generate_runtime_lib_file_bytes(
+            &template("ParaChain/runtime/src/benchmarks.rs.hbs"),
+            &pallets,
+        )
+        .unwrap();
+        assert!(String::from_utf8(benchmarks)
+            .unwrap()
+            .contains("[pallet_nfts, Nfts]"));
+        let apis = generate_runtime_lib_file_bytes(
+            &template("ParaChain/runtime/src/apis.rs.hbs"),
+            &pallets,
+        )
+        .unwrap();
+        let apis = String::from_utf8(apis).unwrap();
+        assert!(apis.contains("\nuse super::Nfts;\n"));
+        assert!(!apis.contains("#[allow(unused_imports)]"));
+        assert!(syn::parse_file(&apis).is_ok());
+        let manifest = generate_manifest_file_to_bytes(
+            &template("SoloChain/runtime/Cargo.toml.hbs"),
+            &pallets,
+            &SdkSources::new(&TemplateType::SoloChain, &SdkVersion::default()),
+        )
+        .unwrap();
+        let manifest = String::from_utf8(manifest).unwrap();
+        assert!(manifest.contains("pallet-nfts-runtime-api = {"));
+        assert!(manifest.contains("\"pallet-nfts-runtime-api/std\","));
+        assert!(manifest.contains("\"pallet-nfts/runtime-benchmarks\","));
+        assert!(manifest.contains("\"pallet-nfts/try-runtime\","));
+        assert!(!manifest.contains("\"pallet-nfts-runtime-api/runtime-benchmarks\","));
+        assert!(!manifest.contains("\"pallet-nfts-runtime-api/try-runtime\","));
+    }
 }
@@ -0,0 +1,110 @@
+use std::{io::Cursor, path::Path, sync::Arc};
+
+use async_zip::tokio::write::ZipFileWriter;
+
+use super::{
+    service::CodeGeneratorService,
+    types::{
+        PalletScaffold, PalletScaffoldCall, PalletScaffoldConstant, PalletScaffoldEvent,
+        PalletScaffoldField, PalletScaffoldStorage,
+    },
+    Catalog, CodeGenerator,
+};
+use crate::services::async_zip::AsyncZipArchiverService;
+
+/// Builds the service from the `CONFIG_DIRECTORY` and `TEMPLATES_DIRECTORY` of `.env.local`.
+pub async fn code_generator() -> CodeGeneratorService<ZipFileWriter<Cursor<Vec<u8>>>> {
+    dotenv::from_filename(".env.local").ok();
+    CodeGeneratorService::try_new(Arc::new(AsyncZipArchiverService))
+        .await
+        .unwrap()
+}
+
+/// The catalog of the checked in configs and templates.
+pub async fn catalog() -> Arc<Catalog> {
+    code_generator().await.catalog()
+}
+
+/// A `.hbs` file of the checked in templates, `path` is relative to the templates directory.
+pub fn template(path: &str) -> String {
+    std::fs::read_to_string(Path::new("templates").join(path)).unwrap()
+}
+
+/// Names brought in by the private `use` items of a rendered file that the rest of the file
+/// never mentions. Glob imports and items marked `#[allow(unused_imports)]` are skipped.
+pub fn unused_imports(source: &str) -> Vec<String> {
+    fn imported_names(tree: &syn::UseTree, parent: Option<&syn::Ident>, names: &mut Vec<String>) {
+        match tree {
+            syn::UseTree::Path(path) => imported_names(&path.tree, Some(&path.ident), names),
+            syn::UseTree::Name(name) if name.ident == "self" => {
+                names.extend(parent.map(ToString::to_string))
+            }
+            syn::UseTree::Name(name) => names.push(name.ident.to_string()),
+            syn::UseTree::Rename(rename) if rename.rename == "_" => {}
+            syn::UseTree::Rename(rename) => names.push(rename.rename.to_string()),
+            syn::UseTree::Glob(_) => {}
+            syn::UseTree::Group(group) => group
+                .items
+                .iter()
+                .for_each(|tree| imported_names(tree, parent, names)),
+        }
+    }
+    let mut file = syn::parse_file(source).unwrap();
+    let mut names = vec![];
+    file.items.retain(|item| {
+        let syn::Item::Use(item_use) = item else {
+            return true;
+        };
+        let allowed = item_use.attrs.iter().any(|attr| {
+            attr.path().is_ident("allow")
+                && attr
+                    .parse_args_with(
+                        syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+                    )
+                    .is_ok_and(|lints| lints.iter().any(|lint| lint.is_ident("unused_imports")))
+        });
+        if !allowed && matches!(item_use.vis, syn::Visibility::Inherited) {
+            imported_names(&item_use.tree, None, &mut names);
+        }
+        false
+    });
+    let rest = prettyplease::unparse(&file);
+    let words = rest
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .collect::<std::collections::HashSet<_>>();
+    names
+        .into_iter()
+        .filter(|name| !words.contains(name.as_str()))
+        .collect()
+}
+
+/// A scaffold using every kind of pallet item.
+pub fn kitty_market_scaffold() -> PalletScaffold {
+    let field = |name: &str, field_type: &str| PalletScaffoldField {
+        name: name.to_string(),
+        field_type: field_type.to_string(),
+    };
+    PalletScaffold {
+        name: "kitty_market".to_string(),
+        description: None,
+        storage: Some(vec![PalletScaffoldStorage {
+            name: "Prices".to_string(),
+            key_type: Some("u32".to_string()),
+            value_type: "u128".to_string(),
+        }]),
+        calls: Some(vec![PalletScaffoldCall {
+            name: "set_price".to_string(),
+            args: Some(vec![field("kitty", "u32"), field("price", "u128")]),
+        }]),
+        events: Some(vec![PalletScaffoldEvent {
+            name: "PriceSet".to_string(),
+            fields: Some(vec![field("kitty", "u32")]),
+        }]),
+        errors: Some(vec!["NoKitty".to_string()]),
+        constants: Some(vec![PalletScaffoldConstant {
+            name: "MaxKitties".to_string(),
+            const_type: "u32".to_string(),
+            value: "100".to_string(),
+        }]),
+    }
+}
@@ -1,30 +1,82 @@
 use poem_openapi::{Enum, Object};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 use strum_macros::{Display, EnumString};
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
 pub struct PalletConstructRuntimeConfig {
     pub runtime: Vec<String>,
+    pub pallet_index: Option<u8>,
+    pub instance_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
 pub struct PalletGenesisConfig {
     pub config_struct_name: String,
     pub struct_fields: HashMap<String, String>,
 }
 
+/// A curated value of an associated type that replaces the one in `pallet_traits`.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
+pub struct PalletTraitAlternative {
+    /// Name the alternative is selected by, e.g. `council_majority`
+    pub name: String,
+    pub description: Option<String>,
+    pub value: String,
+    /// Pallets the value refers to, they must be part of the runtime
+    pub required_pallets: Option<Vec<String>>,
+}
+
+/// Runtime settings that only apply while another pallet is part of the runtime.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
+pub struct ConditionalRuntimeConfig {
+    /// Associated types that replace the ones in `pallet_traits`
+    pub pallet_traits: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
 pub struct PalletRuntimeConfig {
     pub construct_runtime: PalletConstructRuntimeConfig,
     pub pallet_traits: HashMap<String, String>,
+    /// Alternatives keyed by associated type, selectable through `/generate-project`
+    pub pallet_trait_alternatives: Option<HashMap<String, Vec<PalletTraitAlternative>>>,
+    /// Conditional settings keyed by the name of the pallet they depend on
+    pub when: Option<HashMap<String, ConditionalRuntimeConfig>>,
     pub additional_pallet_impl_code: Option<String>,
     pub genesis_config: Option<PalletGenesisConfig>,
     pub additional_chain_spec_code: Option<Vec<String>>,
     pub additional_runtime_lib_code: Option<Vec<String>>,
+    /// `use` lines `runtime_api_code` needs in templates that implement the runtime APIs in
+    /// their own `apis.rs`, for the runtime items that module does not import already
+    pub runtime_api_imports: Option<Vec<String>>,
     pub runtime_api_code: Option<String>,
     pub optional_parameter_types: Option<HashMap<String, ParameterType>>,
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
+pub struct PalletBenchmarksConfig {
+    /// Crate path passed to `define_benchmarks!`, e.g. `pallet_nfts`
+    pub module: String,
+    /// Benchmarked type, the pallet's runtime alias when not set
+    pub runtime_type: Option<String>,
+}
+
+/// RPC wiring added to the node's `create_full`. Templates without a `node/src/rpc.rs.hbs`
+/// reject pallets that set it.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
+pub struct PalletNodeRpcConfig {
+    pub imports: Option<Vec<String>>,
+    pub runtime_api_bounds: Option<Vec<String>>,
+    pub create_full_code: Option<String>,
+    pub dependencies: Option<Vec<CargoComplexDependency>>,
+}
 #[derive(EnumString, Display, Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Enum)]
 #[serde(rename_all = "snake_case")]
 pub enum ParameterTypePrefix {
@@ -37,6 +89,7 @@ pub enum ParameterTypePrefix {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
 pub struct ParameterTypeExpression {
     pub default_unit: String,
     pub default_multiplier: Option<i64>,
@@ -47,23 +100,323 @@ pub struct ParameterTypeExpression {
     pub configured_unit: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum ParameterValueKind {
+    /// Whole number, e.g. `32`
+    Integer,
+    /// Percentage of a `Percent`, `Permill`, `Perbill` or `Perquintill`, e.g. `12.5`
+    Percent,
+    /// `true` or `false`
+    Bool,
+    /// Eight ASCII characters, e.g. `py/trsry`
+    PalletId,
+    /// One of `variants`
+    Enum,
+    /// Possibly fractional amount of `unit`, e.g. `1.5`
+    Balance,
+}
+
+/// Typed value of a parameter, configured with a single `value` instead of a unit and a multiplier.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
+pub struct ParameterValue {
+    pub kind: ParameterValueKind,
+    /// Inclusive lower bound of `integer`, `percent` and `balance` values
+    pub min: Option<i64>,
+    /// Inclusive upper bound of `integer`, `percent` and `balance` values
+    pub max: Option<i64>,
+    /// Rust expressions an `enum` value can take
+    pub variants: Option<Vec<String>>,
+    /// Currency constant a `balance` value is expressed in, e.g. `DOLLARS`
+    pub unit: Option<String>,
+    pub default_value: String,
+    pub configured_value: Option<String>,
+}
+
+/// Splits a decimal like `12.5` into its digits and the number of fractional digits.
+fn parse_decimal(value: &str) -> Option<(i128, u32)> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if fraction.len() > 18 {
+        return None;
+    }
+    let digits = format!("{}{}", whole, fraction);
+    if whole.trim_start_matches('-').is_empty()
+        || !digits
+            .trim_start_matches('-')
+            .chars()
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    Some((digits.parse().ok()?, fraction.len() as u32))
+}
+
+impl ParameterValue {
+    /// Checks a value against the kind and bounds, `p_type` is the declared Rust type.
+    pub fn validate(&self, p_type: &str, value: &str) -> Result<(), String> {
+        match self.kind {
+            ParameterValueKind::Integer => self.check_bounds(&integer_literal(p_type, value)?),
+            ParameterValueKind::Percent => {
+                check_unsigned(value)?;
+                self.check_bounds(value)?;
+                per_thing_parts(p_type, value).map(|_| ())
+            }
+            ParameterValueKind::Balance => {
+                if self.unit.is_none() {
+                    return Err("balance parameters need a unit".to_string());
+                }
+                check_unsigned(value)?;
+                self.check_bounds(value)
+            }
+            ParameterValueKind::Bool => match value {
+                "true" | "false" => Ok(()),
+                _ => Err(format!("{} is not true or false", value)),
+            },
+            ParameterValueKind::PalletId => {
+                if value.len() == 8
+                    && value
+                        .chars()
+                        .all(|c| (c.is_ascii_graphic() || c == ' ') && c != '"' && c != '\\')
+                {
+                    Ok(())
+                } else {
+                    Err(format!("{} is not 8 printable ASCII characters", value))
+                }
+            }
+            ParameterValueKind::Enum => {
+                let variants = self.variants.clone().unwrap_or_default();
+                if variants.iter().any(|variant| variant == value) {
+                    Ok(())
+                } else {
+                    Err(format!("{} is not one of [{}]", value, variants.join(", ")))
+                }
+            }
+        }
+    }
+
+    fn check_bounds(&self, value: &str) -> Result<(), String> {
+        let (digits, scale) =
+            parse_decimal(value).ok_or_else(|| format!("{} is not a number", value))?;
+        let scaled = |bound: i64| i128::from(bound) * 10i128.pow(scale);
+        if self.min.is_some_and(|min| digits < scaled(min)) {
+            return Err(format!(
+                "{} is below {}",
+                value,
+                self.min.unwrap_or_default()
+            ));
+        }
+        if self.max.is_some_and(|max| digits > scaled(max)) {
+            return Err(format!(
+                "{} is above {}",
+                value,
+                self.max.unwrap_or_default()
+            ));
+        }
+        Ok(())
+    }
+
+    /// The configured value, or the default one, as a Rust expression of type `p_type`.
+    pub fn to_rust(&self, p_type: &str) -> String {
+        let value = self
+            .configured_value
+            .as_deref()
+            .unwrap_or(&self.default_value);
+        match self.kind {
+            ParameterValueKind::Integer => {
+                integer_literal(p_type, value).unwrap_or_else(|_| value.to_string())
+            }
+            ParameterValueKind::Bool | ParameterValueKind::Enum => value.to_string(),
+            ParameterValueKind::Percent => match per_thing_parts(p_type, value) {
+                Ok(parts) => format!("{}::from_parts({})", p_type, parts),
+                Err(_) => value.to_string(),
+            },
+            ParameterValueKind::PalletId => format!("PalletId(*b\"{}\")", value),
+            ParameterValueKind::Balance => {
+                let unit = self.unit.clone().unwrap_or_default();
+                match parse_decimal(value) {
+                    Some((digits, 0)) => format!("{} * {}", digits, unit),
+                    Some((digits, scale)) => {
+                        format!("{} * {} / {}", digits, unit, 10i128.pow(scale))
+                    }
+                    None => value.to_string(),
+                }
+            }
+        }
+    }
+}
+
+/// Balances and percentages are unsigned, whatever `min` allows.
+fn check_unsigned(value: &str) -> Result<(), String> {
+    match parse_decimal(value) {
+        Some((digits, _)) if digits < 0 => Err(format!("{} is negative", value)),
+        _ => Ok(()),
+    }
+}
+
+/// Canonical literal of an integer value, e.g. `+007` is `7`. Primitive types and the aliases
+/// every template defines bound the value to their range, other types only need a whole number.
+fn integer_literal(p_type: &str, value: &str) -> Result<String, String> {
+    let literal = match p_type {
+        "u8" => value.parse::<u8>().map(|v| v.to_string()),
+        "u16" => value.parse::<u16>().map(|v| v.to_string()),
+        "u32" | "BlockNumber" | "Nonce" => value.parse::<u32>().map(|v| v.to_string()),
+        "u64" => value.parse::<u64>().map(|v| v.to_string()),
+        "u128" | "Balance" => value.parse::<u128>().map(|v| v.to_string()),
+        "i8" => value.parse::<i8>().map(|v| v.to_string()),
+        "i16" => value.parse::<i16>().map(|v| v.to_string()),
+        "i32" => value.parse::<i32>().map(|v| v.to_string()),
+        "i64" => value.parse::<i64>().map(|v| v.to_string()),
+        "i128" => value.parse::<i128>().map(|v| v.to_string()),
+        _ => {
+            return value
+                .parse::<i128>()
+                .map(|v| v.to_string())
+                .map_err(|_| format!("{} is not an integer", value))
+        }
+    };
+    literal.map_err(|_| format!("{} is not a valid {}", value, p_type))
+}
+
+/// Parts of a per-thing type that make up a percentage, e.g. `12.5` of `Permill` is `125000`.
+fn per_thing_parts(p_type: &str, value: &str) -> Result<i128, String> {
+    let accuracy: i128 = match p_type {
+        "Percent" => 100,
+        "Permill" => 1_000_000,
+        "Perbill" => 1_000_000_000,
+        "Perquintill" => 1_000_000_000_000_000_000,
+        _ => return Err(format!("{} is not a per-thing type", p_type)),
+    };
+    let (digits, scale) =
+        parse_decimal(value).ok_or_else(|| format!("{} is not a number", value))?;
+    let denominator = 100 * 10i128.pow(scale);
+    if !(0..=denominator).contains(&digits) {
+        return Err(format!("{} is not between 0 and 100", value));
+    }
+    if digits * accuracy % denominator != 0 {
+        return Err(format!("{} is too precise for {}", value, p_type));
+    }
+    Ok(digits * accuracy / denominator)
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
 pub struct ParameterType {
     pub name: String,
     pub description: String,
     pub prefix: ParameterTypePrefix,
     pub p_type: String,
-    pub expression: ParameterTypeExpression,
+    /// Unit and multiplier filled into a format string, unused when `value` is set
+    pub expression: Option<ParameterTypeExpression>,
+    pub value: Option<ParameterValue>,
+}
+
+/// A parameter configuration that does not fit the declaration of the parameter.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+pub struct InvalidParameter {
+    pub pallet: String,
+    pub parameter: String,
+    pub reason: String,
+}
+
+impl fmt::Display for InvalidParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.pallet, self.parameter, self.reason)
+    }
 }
 
-#[allow(unused)]
+/// A rendered project file that does not parse. Macro bodies such as `parameter_types!` are
+/// token streams to `syn`, so a broken snippet inside one is only found by `cargo build`.
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+pub struct InvalidGeneratedFile {
+    pub file: String,
+    pub line: u32,
+    pub message: String,
+    /// The pallet whose config contributed the failing line, when it can be told
+    pub pallet: Option<String>,
+}
+
+/// Parts of a project that cannot be turned into valid code, either list may be empty.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, Object)]
+pub struct UnprocessableProject {
+    /// Parameter configurations that do not fit their pallet
+    pub invalid_parameters: Vec<InvalidParameter>,
+    /// Rendered files that do not parse, with the pallet that broke them
+    pub invalid_generated_files: Vec<InvalidGeneratedFile>,
+}
+
+impl fmt::Display for InvalidGeneratedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.file, self.line, self.message)?;
+        if let Some(pallet) = &self.pallet {
+            write!(f, " (from {})", pallet)?;
+        }
+        Ok(())
+    }
+}
+
+/// A pallet that the chosen template does not support.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+pub struct UnsupportedPallet {
+    pub pallet: String,
+    pub template: TemplateType,
+    /// Set when the pallet was pulled in through `required_pallets`
+    pub required_by: Option<String>,
+    /// Variant of the same pallet crate that supports the template
+    pub suggestion: Option<String>,
+}
+
+/// Why a selection of pallets cannot be built on the template, either list may be empty.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq, Object)]
+pub struct UnbuildableSelection {
+    /// Selected or required pallets that the template does not support
+    pub unsupported_pallets: Vec<UnsupportedPallet>,
+    /// Pairs of selected or required pallets that cannot be used together
+    pub conflicts: Vec<String>,
+}
+
+impl fmt::Display for UnsupportedPallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} does not support {}", self.pallet, self.template)?;
+        if let Some(required_by) = &self.required_by {
+            write!(f, " (required by {})", required_by)?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ", use {} instead", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
 pub struct CargoSimpleDependency {
-    package: String,
-    version: String,
+    pub package: String,
+    pub version: String,
+    /// Runtime features forwarded to this crate, none when not set
+    pub runtime_features: Option<Vec<RuntimeFeature>>,
+}
+
+impl CargoSimpleDependency {
+    pub fn has_runtime_feature(&self, feature: RuntimeFeature) -> bool {
+        self.runtime_features
+            .as_ref()
+            .is_some_and(|features| features.contains(&feature))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Enum)]
+#[serde(rename_all = "kebab-case")]
+#[oai(rename_all = "kebab-case")]
+pub enum RuntimeFeature {
+    Std,
+    RuntimeBenchmarks,
+    TryRuntime,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
 pub struct CargoComplexDependency {
     pub package: String,
     pub version: Option<String>,
@@ -72,28 +425,110 @@ pub struct CargoComplexDependency {
     pub git_repo: Option<String>,
     pub tag: Option<String>,
     pub branch: Option<String>,
+    pub rev: Option<String>,
+    pub path: Option<String>,
+    /// Cargo features enabled on the crate
+    pub features: Option<Vec<String>>,
+    /// Runtime features forwarded to this crate, all of them when not set
+    pub runtime_features: Option<Vec<RuntimeFeature>>,
 }
 
 impl CargoComplexDependency {
     pub fn name_cebab_case(&self) -> String {
         self.alias.to_lowercase().replace(" ", "-")
     }
+
+    /// Checks that the dependency names exactly one source: crates.io, git or path.
+    pub fn validate(&self) -> Result<(), String> {
+        let git_refs = [&self.tag, &self.branch, &self.rev]
+            .iter()
+            .filter(|git_ref| git_ref.is_some())
+            .count();
+        match (&self.version, &self.git_repo, &self.path) {
+            (_, Some(_), Some(_)) => {
+                Err(format!("{} sets both `git_repo` and `path`", self.package))
+            }
+            (Some(_), Some(_), _) => Err(format!(
+                "{} sets both `version` and `git_repo`",
+                self.package
+            )),
+            (_, Some(_), None) if git_refs > 1 => Err(format!(
+                "{} sets more than one of `tag`, `branch` and `rev`",
+                self.package
+            )),
+            (_, None, _) if git_refs > 0 => Err(format!(
+                "{} sets `tag`, `branch` or `rev` without `git_repo`",
+                self.package
+            )),
+            (None, None, None) => Err(format!(
+                "{} sets none of `version`, `git_repo` and `path`",
+                self.package
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn has_runtime_feature(&self, feature: RuntimeFeature) -> bool {
+        self.runtime_features
+            .as_ref()
+            .is_none_or(|features| features.contains(&feature))
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
 pub struct PalletDependencyConfig {
     pub pallet: CargoComplexDependency,
     pub additional_pallets: Option<Vec<CargoComplexDependency>>,
     pub additional_deps: Option<Vec<CargoSimpleDependency>>,
     pub required_pallets: Option<Vec<String>>,
+    /// Pallets that cannot be part of the same runtime
+    pub conflicts_with: Option<Vec<String>>,
+}
+
+/// polkadot-sdk release a project is generated against. Templates and pallets list the releases
+/// they support, so a release is only usable once something declares it.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    Hash,
+    Enum,
+    EnumString,
+    Display,
+)]
+pub enum SdkVersion {
+    #[default]
+    #[serde(rename = "polkadot-v1.14.0")]
+    #[oai(rename = "polkadot-v1.14.0")]
+    #[strum(serialize = "polkadot-v1.14.0")]
+    PolkadotV1_14_0,
+    #[serde(rename = "polkadot-stable2409")]
+    #[oai(rename = "polkadot-stable2409")]
+    #[strum(serialize = "polkadot-stable2409")]
+    PolkadotStable2409,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Enum)]
-pub enum SubstrateVersion {
-    One,
-    Two,
+impl SdkVersion {
+    /// polkadot-sdk branch of the release, also used by Frontier for its matching branch.
+    pub fn stable_branch(&self) -> &'static str {
+        match self {
+            SdkVersion::PolkadotV1_14_0 => "stable2407",
+            SdkVersion::PolkadotStable2409 => "stable2409",
+        }
+    }
 }
 
+/// Repository whose crates follow the selected [`SdkVersion`].
+pub const POLKADOT_SDK_REPO: &str = "https://github.com/paritytech/polkadot-sdk.git";
+/// Repository of the Frontier crates, which only publishes a branch per release.
+pub const FRONTIER_REPO: &str = "https://github.com/polkadot-evm/frontier";
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq, Display, Enum)]
 pub enum PalletCategory {
     Accounts,
@@ -135,6 +570,16 @@ pub enum TemplateType {
     Frontier,
 }
 
+impl TemplateType {
+    /// The Frontier templates pin polkadot-sdk by the stable branch instead of the release tag.
+    pub fn follows_stable_branch(&self) -> bool {
+        matches!(
+            self,
+            TemplateType::Frontier | TemplateType::SoloFrontierChain
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Eq, Enum)]
 pub enum CommonAuthors {
     ParityTechnologies,
@@ -143,24 +588,130 @@ pub enum CommonAuthors {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
 pub struct PalletMetadata {
     pub description: String,
     pub short_description: String,
-    pub compatibility: SubstrateVersion,
+    pub sdk_versions: Vec<SdkVersion>,
     pub license: Option<String>,
     pub authors: Vec<CommonAuthors>,
     pub category: Option<PalletCategory>,
     pub size: usize,
+    /// Unix timestamp of the last review of the config
+    pub updated: Option<String>,
     pub is_essential: Option<Vec<TemplateType>>,
     pub supported_template: Vec<TemplateType>,
     pub is_instance: Option<bool>,
+    /// Whether `/generate-project` may add named instances of this pallet
+    pub is_instantiable: Option<bool>,
     pub use_cases: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+pub struct PalletScaffoldField {
+    pub name: String,
+    pub field_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+pub struct PalletScaffoldStorage {
+    pub name: String,
+    /// Makes the item a `StorageMap` keyed by this type instead of a `StorageValue`
+    pub key_type: Option<String>,
+    pub value_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+pub struct PalletScaffoldCall {
+    pub name: String,
+    pub args: Option<Vec<PalletScaffoldField>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+pub struct PalletScaffoldEvent {
+    pub name: String,
+    pub fields: Option<Vec<PalletScaffoldField>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+pub struct PalletScaffoldConstant {
+    pub name: String,
+    pub const_type: String,
+    pub value: String,
+}
+
+/// Declarative description of a new pallet crate generated under `pallets/<name>`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+pub struct PalletScaffold {
+    /// snake_case name, the crate becomes `pallet-<name>`
+    pub name: String,
+    pub description: Option<String>,
+    pub storage: Option<Vec<PalletScaffoldStorage>>,
+    pub calls: Option<Vec<PalletScaffoldCall>>,
+    pub events: Option<Vec<PalletScaffoldEvent>>,
+    pub errors: Option<Vec<String>>,
+    pub constants: Option<Vec<PalletScaffoldConstant>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Object)]
+#[serde(deny_unknown_fields)]
 pub struct PalletConfig {
     pub name: String,
     pub metadata: PalletMetadata,
     pub runtime: PalletRuntimeConfig,
     pub dependencies: PalletDependencyConfig,
+    pub node_rpc: Option<PalletNodeRpcConfig>,
+    pub benchmarks: Option<PalletBenchmarksConfig>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_integer_values() {
+        let integer = ParameterValue {
+            kind: ParameterValueKind::Integer,
+            min: None,
+            max: Some(500),
+            variants: None,
+            unit: None,
+            default_value: "1".to_string(),
+            configured_value: Some("+007".to_string()),
+        };
+        assert!(integer.validate("u32", "-5").is_err());
+        assert!(integer.validate("u8", "1000").is_err());
+        assert!(integer.validate("u8", "256").is_err());
+        assert!(integer.validate("u16", "1000").is_err());
+        assert!(integer.validate("u16", "1.5").is_err());
+        assert!(integer.validate("BlockNumber", "5x").is_err());
+        assert!(integer.validate("i32", "-5").is_ok());
+        assert!(integer.validate("BlockNumber", "-5").is_err());
+        assert!(integer.validate("Count", "-5").is_ok());
+        assert!(integer.validate("u32", "+007").is_ok());
+        assert_eq!(integer.to_rust("u32"), "7");
+    }
+
+    #[test]
+    fn test_negative_values() {
+        let balance = ParameterValue {
+            kind: ParameterValueKind::Balance,
+            min: None,
+            max: None,
+            variants: None,
+            unit: Some("DOLLARS".to_string()),
+            default_value: "1".to_string(),
+            configured_value: None,
+        };
+        assert!(balance.validate("Balance", "-5").is_err());
+        assert!(balance.validate("Balance", "-0.5").is_err());
+        assert!(balance.validate("Balance", "0.5").is_ok());
+        let percent = ParameterValue {
+            kind: ParameterValueKind::Percent,
+            min: Some(-10),
+            unit: None,
+            ..balance
+        };
+        assert!(percent.validate("Permill", "-1").is_err());
+        assert!(percent.validate("Permill", "1").is_ok());
+    }
 }
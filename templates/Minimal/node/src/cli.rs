@@ -0,0 +1,73 @@
+use sc_cli::RunCmd;
+
+/// How the node seals blocks, there is no block authoring consensus in this template.
+#[derive(Debug, Clone)]
+pub enum Consensus {
+    /// Seal a block every given number of milliseconds.
+    ManualSeal(u64),
+    /// Seal a block as soon as a transaction arrives.
+    InstantSeal,
+}
+
+impl std::str::FromStr for Consensus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(if s == "instant-seal" {
+            Consensus::InstantSeal
+        } else if let Some(block_time) = s.strip_prefix("manual-seal-") {
+            Consensus::ManualSeal(block_time.parse().map_err(|_| "invalid block time")?)
+        } else {
+            return Err("incorrect consensus identifier".into());
+        })
+    }
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub subcommand: Option<Subcommand>,
+
+    /// `instant-seal`, or `manual-seal-<milliseconds>` to seal blocks at a fixed interval.
+    #[clap(long, default_value = "manual-seal-6000")]
+    pub consensus: Consensus,
+
+    #[clap(flatten)]
+    pub run: RunCmd,
+}
+
+#[derive(Debug, clap::Subcommand)]
+#[allow(clippy::large_enum_variant)]
+pub enum Subcommand {
+    /// Key management cli utilities
+    #[command(subcommand)]
+    Key(sc_cli::KeySubcommand),
+
+    /// Build a chain specification.
+    BuildSpec(sc_cli::BuildSpecCmd),
+
+    /// Validate blocks.
+    CheckBlock(sc_cli::CheckBlockCmd),
+
+    /// Export blocks.
+    ExportBlocks(sc_cli::ExportBlocksCmd),
+
+    /// Export the state of a given block into a chain spec.
+    ExportState(sc_cli::ExportStateCmd),
+
+    /// Import blocks.
+    ImportBlocks(sc_cli::ImportBlocksCmd),
+
+    /// Remove the whole chain.
+    PurgeChain(sc_cli::PurgeChainCmd),
+
+    /// Revert the chain to a previous state.
+    Revert(sc_cli::RevertCmd),
+
+    /// Sub-commands concerned with benchmarking.
+    #[command(subcommand)]
+    Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+    /// Db meta columns information.
+    ChainInfo(sc_cli::ChainInfoCmd),
+}
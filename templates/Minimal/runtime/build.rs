@@ -0,0 +1,6 @@
+fn main() {
+    #[cfg(feature = "std")]
+    {
+        substrate_wasm_builder::WasmBuilder::build_using_defaults();
+    }
+}
@@ -1,6 +1,9 @@
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
-use poem_openapi::{param::Path, payload::Json};
+use poem_openapi::{
+    param::{Path, Query},
+    payload::Json,
+};
 use prometheus::Registry;
 use substrate_runtime_builder::{
     api::{
@@ -10,6 +13,7 @@ use substrate_runtime_builder::{
             },
             get_status_handler::{GetStatusResponse, Status},
             get_templates_handler::GetTemplatesResponse,
+            preview_project_handler::PreviewProjectResponse,
         },
         Api,
     },
@@ -47,42 +51,57 @@ async fn test_api() {
     let api = boot_api().await;
     assert!(api.is_ok());
     let api = api.unwrap();
-    let sp = api.get_templates(Path(TemplateType::SoloChain)).await;
-    let bt = match sp {
-        GetTemplatesResponse::Ok(Json(bt)) => bt,
-        GetTemplatesResponse::NotFound(_) => panic!("Templates not found"),
-    };
-    let request_sp = bt
-        .supported_pallets
-        .iter()
-        .map(|pallet| (pallet.name.clone(), None))
-        .collect::<HashMap<String, Option<HashMap<String, ParameterConfiguration>>>>();
-    let req_body = "{\"name\": \"test_project\", \"template\": \"SoloChain\",\"pallets\": "
-        .to_string()
-        + &serde_json::to_string(&request_sp).unwrap()
-        + "}";
-    let project = serde_json::from_str::<NewProject>(&req_body);
-    assert!(project.is_ok());
-    let project = project.unwrap();
-    let gp = api.generate_a_project(Json(project)).await;
-    let response = match gp {
-        GenerateProjectResponse::Ok(response) => response.0,
-        _ => panic!("Expected Ok response"),
-    };
-    let status = loop {
-        let status = api.get_status(Path(response)).await;
-        match status {
-            GetStatusResponse::Ok(sr) => match sr.0.status {
-                Status::Finished => break sr.0,
-                _ => tokio::time::sleep(Duration::from_millis(100)).await,
-            },
+    // the minimal template has no consensus pallets, so its projects are smaller
+    for (template, min_content_length) in [
+        (TemplateType::SoloChain, 44000),
+        (TemplateType::Minimal, 40000),
+    ] {
+        let sp = api.get_templates(Path(template.clone()), Query(None)).await;
+        let bt = match sp {
+            GetTemplatesResponse::Ok(Json(bt)) => bt,
+            GetTemplatesResponse::NotFound(_) => panic!("Templates not found"),
+        };
+        let request_sp = bt
+            .supported_pallets
+            .iter()
+            .map(|pallet| (pallet.name.clone(), None))
+            .collect::<HashMap<String, Option<HashMap<String, ParameterConfiguration>>>>();
+        let req_body = format!(
+            "{{\"name\": \"test_project\", \"template\": \"{}\",\"pallets\": {}}}",
+            template,
+            serde_json::to_string(&request_sp).unwrap()
+        );
+        let preview = api
+            .preview_project(Json(serde_json::from_str::<NewProject>(&req_body).unwrap()))
+            .await;
+        let files = match preview {
+            PreviewProjectResponse::Ok(files) => files.0,
+            _ => panic!("Expected Ok response"),
+        };
+        assert!(files.iter().any(|file| file.path == "runtime/src/lib.rs"));
+        let project = serde_json::from_str::<NewProject>(&req_body);
+        assert!(project.is_ok());
+        let project = project.unwrap();
+        let gp = api.generate_a_project(Json(project)).await;
+        let response = match gp {
+            GenerateProjectResponse::Ok(response) => response.0,
             _ => panic!("Expected Ok response"),
-        }
-    };
-    assert!(status.url.is_some());
-    let response = reqwest::get(status.url.unwrap()).await;
-    assert!(response.is_ok());
-    let response = response.unwrap();
-    assert!(response.status().is_success());
-    assert!(response.content_length().unwrap() > 44000);
+        };
+        let status = loop {
+            let status = api.get_status(Path(response)).await;
+            match status {
+                GetStatusResponse::Ok(sr) => match sr.0.status {
+                    Status::Finished => break sr.0,
+                    _ => tokio::time::sleep(Duration::from_millis(100)).await,
+                },
+                _ => panic!("Expected Ok response"),
+            }
+        };
+        assert!(status.url.is_some());
+        let response = reqwest::get(status.url.unwrap()).await;
+        assert!(response.is_ok());
+        let response = response.unwrap();
+        assert!(response.status().is_success());
+        assert!(response.content_length().unwrap() > min_content_length);
+    }
 }